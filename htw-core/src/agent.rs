@@ -0,0 +1,503 @@
+pub mod agent {
+    use crate::direction::direction::Direction;
+    use crate::htw_game::htw_game::GameStatus;
+    use crate::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+    use crate::inference::inference::Assessment;
+    use crate::knowledge_map::knowledge_map::{KnowledgeMap, WUMPUS};
+    use crate::HuntTheWumpus;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    // the risk of a passage nothing is known about.
+    const UNKNOWN_RISK: f64 = 0.5;
+    // a cavern with no more passages than this is good enough to wait in for the wumpus.
+    const CAMP_PASSAGES: usize = 2;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum AgentCommand {
+        Move(Direction),
+        Shoot(Direction),
+        Rest,
+        // gives the game up, which leaves it unfinished.
+        Quit,
+    }
+
+    impl AgentCommand {
+        // `move`, `shoot` and `rest`, or their initials, with a direction or its initial.
+        pub fn parse(text: &str) -> Result<AgentCommand, String> {
+            let words: Vec<String> = text.split_whitespace().map(|w| w.to_lowercase()).collect();
+            let direction = || match words.get(1) {
+                Some(word) if words.len() == 2 => {
+                    Direction::parse(word).ok_or_else(|| format!("unknown direction \"{}\"", word))
+                }
+                _ => Err(format!("\"{}\" needs one direction", words[0])),
+            };
+            match words.first().map(|w| w.as_str()) {
+                Some("move" | "m") => direction().map(AgentCommand::Move),
+                Some("shoot" | "s") => direction().map(AgentCommand::Shoot),
+                Some("rest" | "r") if words.len() == 1 => Ok(AgentCommand::Rest),
+                _ => Err(format!("unknown command \"{}\"", text)),
+            }
+        }
+    }
+
+    // what a player at the terminal would know at the start of a turn.
+    pub struct Observation<'a> {
+        pub cavern: &'a str,
+        // what the game said since the last command, ending with the passages and percepts.
+        pub messages: &'a [HtwMessage],
+        pub directions: Vec<Direction>,
+        pub hit_points: u32,
+        pub max_hit_points: u32,
+        pub arrows: u32,
+        pub knowledge_map: &'a KnowledgeMap,
+        // what the `hint` command would tell.
+        pub assessments: &'a [Assessment],
+    }
+
+    impl Observation<'_> {
+        pub fn sensed(&self, message: &HtwMessage) -> bool {
+            self.messages.contains(message)
+        }
+    }
+
+    pub trait Agent {
+        fn name(&self) -> &str;
+        fn act(&mut self, observation: &Observation) -> AgentCommand;
+        // called once the game is over, or given up.
+        fn game_over(&mut self, _outcome: &Outcome) {}
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Outcome {
+        // still `Playing` when the turns ran out or the agent quit.
+        pub status: GameStatus,
+        pub turns: u32,
+        pub hit_points: u32,
+        pub arrows: u32,
+        pub arrows_shot: u32,
+    }
+
+    // lets the agent play until the game ends or `max_turns` have been played.
+    // the game must send its messages to `log`.
+    pub fn run_agent(
+        game: &mut dyn HuntTheWumpus,
+        log: &HtwMessageLog,
+        agent: &mut dyn Agent,
+        max_turns: u32,
+    ) -> Outcome {
+        let mut messages = log.take_messages();
+        if messages.is_empty() {
+            game.report_status();
+            messages = log.take_messages();
+        }
+        let (mut turns, mut arrows_shot) = (0, 0);
+        while turns < max_turns && game.get_status() == &GameStatus::Playing {
+            let assessments = game.assess_caverns();
+            let observation = Observation {
+                cavern: game.get_player_cavern(),
+                messages: &messages,
+                directions: messages
+                    .iter()
+                    .filter_map(|message| match message {
+                        HtwMessage::Passage(direction) => Some(*direction),
+                        _ => None,
+                    })
+                    .collect(),
+                hit_points: game.get_hit_points(),
+                max_hit_points: game.get_max_hit_points(),
+                arrows: game.get_quiver(),
+                knowledge_map: game.knowledge_map(),
+                assessments: &assessments,
+            };
+            match agent.act(&observation) {
+                AgentCommand::Move(direction) => game.make_move_command(direction),
+                AgentCommand::Shoot(direction) => {
+                    if game.get_quiver() > 0 {
+                        arrows_shot += 1;
+                    }
+                    game.make_shoot_command(direction)
+                }
+                AgentCommand::Rest => game.make_rest_command(),
+                AgentCommand::Quit => break,
+            }
+            game.execute_command();
+            messages = log.take_messages();
+            turns += 1;
+        }
+        let outcome = Outcome {
+            status: game.get_status().clone(),
+            turns,
+            hit_points: game.get_hit_points(),
+            arrows: game.get_quiver(),
+            arrows_shot,
+        };
+        agent.game_over(&outcome);
+        outcome
+    }
+
+    // walks about at random, and shoots a random way when it smells the wumpus.
+    pub struct RandomAgent {
+        rng: StdRng,
+    }
+
+    impl RandomAgent {
+        pub fn new(seed: u64) -> RandomAgent {
+            RandomAgent {
+                rng: StdRng::seed_from_u64(seed),
+            }
+        }
+    }
+
+    impl Agent for RandomAgent {
+        fn name(&self) -> &str {
+            "random"
+        }
+
+        fn act(&mut self, observation: &Observation) -> AgentCommand {
+            let Some(direction) = observation.directions.choose(&mut self.rng) else {
+                return AgentCommand::Rest;
+            };
+            if observation.sensed(&HtwMessage::SmellWumpus) && observation.arrows > 0 {
+                AgentCommand::Shoot(*direction)
+            } else {
+                AgentCommand::Move(*direction)
+            }
+        }
+    }
+
+    // explores along the safest passages it knows, using the same inference as `hint`, until
+    // it finds a cavern with few passages to wait in for the wumpus. it shoots where the wumpus
+    // most likely is.
+    #[derive(Default)]
+    pub struct LogicalAgent {}
+
+    impl LogicalAgent {
+        pub fn new() -> LogicalAgent {
+            LogicalAgent {}
+        }
+
+        // the chance that walking `direction` from `cavern` leads into danger.
+        fn risk(observation: &Observation, cavern: &str, direction: Direction) -> f64 {
+            let via = Some((String::from(cavern), direction));
+            match observation.assessments.iter().find(|a| a.via == via) {
+                Some(assessment) => assessment.threats.iter().map(|t| t.probability).sum(),
+                None => UNKNOWN_RISK,
+            }
+        }
+
+        // the neighbour most likely to hold the wumpus, and how likely.
+        fn wumpus_direction(observation: &Observation) -> Option<(Direction, f64)> {
+            let knowledge_map = observation.knowledge_map;
+            let mut best: Option<(Direction, f64)> = None;
+            for direction in &observation.directions {
+                let via = Some((String::from(observation.cavern), *direction));
+                let neighbour = knowledge_map.destination(observation.cavern, *direction);
+                let probability = observation
+                    .assessments
+                    .iter()
+                    .find(|a| match neighbour {
+                        Some(neighbour) => a.cavern.as_deref() == Some(neighbour),
+                        None => a.via == via,
+                    })
+                    .and_then(|a| a.threats.iter().find(|t| t.name == WUMPUS))
+                    .map_or(0.0, |t| t.probability);
+                if best.is_none_or(|(_, p)| probability > p) {
+                    best = Some((*direction, probability));
+                }
+            }
+            best
+        }
+
+        // the caverns the player can walk to without crossing one where something was run
+        // into, nearest first, each with the first step there and the number of steps.
+        fn routes(observation: &Observation) -> Vec<(String, Option<Direction>, u32)> {
+            let knowledge_map = observation.knowledge_map;
+            let mut routes = vec![(String::from(observation.cavern), None, 0)];
+            let mut i = 0;
+            while i < routes.len() {
+                let (cavern, first_step, distance) = routes[i].clone();
+                let passages = knowledge_map
+                    .cavern(&cavern)
+                    .map_or(vec![], |k| k.passages.clone());
+                for direction in passages {
+                    let Some(next) = knowledge_map.destination(&cavern, direction) else {
+                        continue;
+                    };
+                    let safe = knowledge_map
+                        .cavern(next)
+                        .is_some_and(|k| k.found.is_empty());
+                    if safe && !routes.iter().any(|(c, _, _)| c == next) {
+                        routes.push((
+                            String::from(next),
+                            first_step.or(Some(direction)),
+                            distance + 1,
+                        ));
+                    }
+                }
+                i += 1;
+            }
+            routes
+        }
+
+        // the first step towards the least risky passage not walked yet, the nearest first
+        // among equals.
+        fn explore(
+            observation: &Observation,
+            routes: &[(String, Option<Direction>, u32)],
+        ) -> Option<Direction> {
+            let knowledge_map = observation.knowledge_map;
+            let mut best: Option<(f64, u32, Direction)> = None;
+            for (cavern, first_step, distance) in routes {
+                let Some(knowledge) = knowledge_map.cavern(cavern) else {
+                    continue;
+                };
+                for direction in &knowledge.passages {
+                    if knowledge_map.destination(cavern, *direction).is_some() {
+                        continue;
+                    }
+                    let risk = LogicalAgent::risk(observation, cavern, *direction);
+                    if risk < 1.0 && best.is_none_or(|(r, d, _)| (risk, *distance) < (r, d)) {
+                        best = Some((risk, *distance, first_step.unwrap_or(*direction)));
+                    }
+                }
+            }
+            best.map(|(_, _, step)| step)
+        }
+
+        // the cavern with the fewest passages to wait in, the nearest first among equals.
+        // the wumpus is always smelled before it comes, and the fewer the passages, the
+        // likelier the arrow hits it.
+        fn camp<'a>(
+            observation: &Observation,
+            routes: &'a [(String, Option<Direction>, u32)],
+        ) -> Option<&'a (String, Option<Direction>, u32)> {
+            routes
+                .iter()
+                .filter_map(|route| {
+                    let knowledge = observation.knowledge_map.cavern(&route.0)?;
+                    knowledge
+                        .observed
+                        .then_some((knowledge.passages.len(), route))
+                })
+                .min_by_key(|(passages, route)| (*passages, route.2))
+                .map(|(_, route)| route)
+        }
+    }
+
+    impl Agent for LogicalAgent {
+        fn name(&self) -> &str {
+            "logical"
+        }
+
+        fn act(&mut self, observation: &Observation) -> AgentCommand {
+            let smelled = observation.sensed(&HtwMessage::SmellWumpus);
+            if smelled && observation.arrows > 0 {
+                if let Some((direction, _)) = LogicalAgent::wumpus_direction(observation) {
+                    return AgentCommand::Shoot(direction);
+                }
+            }
+            let wounded = observation.hit_points * 3 <= observation.max_hit_points;
+            if wounded && !smelled {
+                return AgentCommand::Rest;
+            }
+            let routes = LogicalAgent::routes(observation);
+            let camp = LogicalAgent::camp(observation, &routes);
+            let good_camp = camp.filter(|(cavern, _, _)| {
+                observation
+                    .knowledge_map
+                    .cavern(cavern)
+                    .is_some_and(|k| k.passages.len() <= CAMP_PASSAGES)
+            });
+            // without arrows there is no point waiting, so look around for some instead.
+            let step = match (observation.arrows, good_camp) {
+                (0, _) | (_, None) => LogicalAgent::explore(observation, &routes)
+                    .or_else(|| camp.and_then(|(_, step, _)| *step)),
+                (_, Some((_, step, _))) => *step,
+            };
+            match step {
+                Some(direction) => AgentCommand::Move(direction),
+                None => AgentCommand::Rest,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_agent {
+        use super::*;
+        use crate::connection::connection::Connection;
+        use crate::connections::connections::Connections;
+        use crate::game_builder::game_builder::GameBuilder;
+        use crate::htw_game::htw_game::Caverns;
+        use crate::inference::inference::Inference;
+        use alloc::boxed::Box;
+
+        // a row of caverns linked west to east.
+        fn row(names: &[&str]) -> (Caverns, Connections) {
+            let mut connections = vec![];
+            for pair in names.windows(2) {
+                connections.push(Connection::new(pair[0], pair[1], &Direction::East));
+                connections.push(Connection::new(pair[1], pair[0], &Direction::West));
+            }
+            let caverns = names.iter().map(|name| String::from(*name)).collect();
+            (caverns, Connections::new(connections))
+        }
+
+        fn observe(
+            map: &mut KnowledgeMap,
+            connections: &Connections,
+            cavern: &str,
+            percepts: &[&str],
+        ) {
+            map.observe(
+                cavern,
+                connections.report_available_directions(cavern),
+                percepts.iter().map(|p| String::from(*p)).collect(),
+            );
+        }
+
+        // b - a - c - d, and x north of a. the player heard pits in a and walked from c.
+        fn set_up() -> (Connections, KnowledgeMap) {
+            let (_, mut connections) = row(&["b", "a", "c", "d"]);
+            connections
+                .connections
+                .push(Connection::new("a", "x", &Direction::North));
+            connections
+                .connections
+                .push(Connection::new("x", "a", &Direction::South));
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "c", &[]);
+            map.link("c", Direction::West, "a");
+            observe(&mut map, &connections, "a", &["pits"]);
+            (connections, map)
+        }
+
+        fn act(connections: &Connections, map: &KnowledgeMap, cavern: &str) -> AgentCommand {
+            let assessments = Inference::new(map, cavern).assess();
+            let observation = Observation {
+                cavern,
+                messages: &[],
+                directions: connections.report_available_directions(cavern),
+                hit_points: 10,
+                max_hit_points: 10,
+                arrows: 5,
+                knowledge_map: map,
+                assessments: &assessments,
+            };
+            LogicalAgent::new().act(&observation)
+        }
+
+        #[test]
+        fn test_parse_command() {
+            assert_eq!(
+                Ok(AgentCommand::Move(Direction::North)),
+                AgentCommand::parse("move n")
+            );
+            assert_eq!(
+                Ok(AgentCommand::Shoot(Direction::East)),
+                AgentCommand::parse("SHOOT East")
+            );
+            assert_eq!(Ok(AgentCommand::Rest), AgentCommand::parse("rest"));
+            assert_eq!(
+                Ok(AgentCommand::Move(Direction::West)),
+                AgentCommand::parse("m w")
+            );
+            assert_eq!(
+                Err(String::from("unknown direction \"up\"")),
+                AgentCommand::parse("move up")
+            );
+            assert_eq!(
+                Err(String::from("\"shoot\" needs one direction")),
+                AgentCommand::parse("shoot")
+            );
+            assert_eq!(
+                Err(String::from("unknown command \"dance n\"")),
+                AgentCommand::parse("dance n")
+            );
+        }
+
+        #[test]
+        fn test_logical_agent_avoids_pit() {
+            let (connections, map) = set_up();
+            let assessments = Inference::new(&map, "a").assess();
+            let observation = Observation {
+                cavern: "a",
+                messages: &[HtwMessage::HearPit],
+                directions: connections.report_available_directions("a"),
+                hit_points: 10,
+                max_hit_points: 10,
+                arrows: 5,
+                knowledge_map: &map,
+                assessments: &assessments,
+            };
+            let routes = LogicalAgent::routes(&observation);
+            // the pit is in b or x, while d lies beyond c, where nothing was heard.
+            assert_eq!(
+                Some(Direction::East),
+                LogicalAgent::explore(&observation, &routes)
+            );
+        }
+
+        #[test]
+        fn test_logical_agent_waits_in_dead_end() {
+            let (connections, mut map) = set_up();
+            map.link("c", Direction::East, "d");
+            observe(&mut map, &connections, "d", &[]);
+            assert_eq!(
+                AgentCommand::Move(Direction::East),
+                act(&connections, &map, "a")
+            );
+            assert_eq!(AgentCommand::Rest, act(&connections, &map, "d"));
+        }
+
+        #[test]
+        fn test_logical_agent_shoots_wumpus() {
+            let (caverns, connections) = row(&["a", "b"]);
+            let log = HtwMessageLog::new();
+            let mut game = GameBuilder::new(caverns)
+                .connections(connections)
+                .player_cavern("a")
+                .wumpus_cavern("b")
+                .quiver(1)
+                .message_receiver(Box::new(log.clone()))
+                .seed(0)
+                .build()
+                .unwrap();
+            let outcome = run_agent(game.as_mut(), &log, &mut LogicalAgent::new(), 10);
+            assert_eq!(
+                Outcome {
+                    status: GameStatus::Won,
+                    turns: 1,
+                    hit_points: 10,
+                    arrows: 1,
+                    arrows_shot: 1,
+                },
+                outcome
+            );
+        }
+
+        #[test]
+        fn test_random_agent_stops_after_max_turns() {
+            let (caverns, connections) = row(&["a", "b", "c", "d", "e"]);
+            let log = HtwMessageLog::new();
+            let mut game = GameBuilder::new(caverns)
+                .connections(connections)
+                .player_cavern("a")
+                .wumpus_cavern("e")
+                .message_receiver(Box::new(log.clone()))
+                .seed(1)
+                .build()
+                .unwrap();
+            let outcome = run_agent(game.as_mut(), &log, &mut RandomAgent::new(1), 3);
+            assert!(outcome.turns <= 3);
+            if outcome.turns < 3 {
+                assert_ne!(GameStatus::Playing, outcome.status);
+            }
+        }
+    }
+}
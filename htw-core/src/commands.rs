@@ -2,26 +2,61 @@ pub mod commands {
     use crate::connection::connection::Connection;
     use crate::connections::connections::Connections;
     use crate::direction::direction::Direction;
-    use crate::htw_game::htw_game::{ArrowsIn, BatCaverns, Caverns, Command, PitCaverns};
+    use crate::htw_game::htw_game::{ArrowsIn, Command, CommandResult, DeathCause};
     use crate::HtwMessageReceiver;
-    use rand::Rng;
-    use std::collections::{HashMap, HashSet};
+    use alloc::collections::BTreeMap;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
 
-    pub struct RestCommand {}
+    pub struct RestCommand {
+        healing: u32,
+    }
+    impl RestCommand {
+        pub fn new(healing: u32) -> RestCommand {
+            RestCommand { healing }
+        }
+    }
     impl Command for RestCommand {
         fn process_command(
             &self,
-            _message_receiver: &Box<dyn HtwMessageReceiver>,
+            _message_receiver: &dyn HtwMessageReceiver,
             _connections: &Connections,
-            _caverns: &Caverns,
-            _player_cavern: &String,
-            _wumpus_cavern: &String,
-            _pit_caverns: &PitCaverns,
-            _bat_caverns: &BatCaverns,
+            _player_cavern: &str,
+            _wumpus_cavern: &str,
             _quiver: u32,
             _arrows_in: &ArrowsIn,
-        ) -> (Option<String>, Option<u32>, Option<ArrowsIn>, Option<u32>) {
-            (None, None, None, None)
+        ) -> CommandResult {
+            CommandResult {
+                healing: Some(self.healing),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_rest_command {
+        use super::*;
+        use crate::htw_message::htw_message::HtwMessageLog;
+        use alloc::vec;
+
+        #[test]
+        fn test_process_command_heals() {
+            let command = RestCommand::new(2);
+            let result = command.process_command(
+                &HtwMessageLog::new(),
+                &Connections::new(vec![]),
+                "cavern",
+                "cavern_w",
+                5,
+                &BTreeMap::new(),
+            );
+            assert_eq!(
+                CommandResult {
+                    healing: Some(2),
+                    ..Default::default()
+                },
+                result
+            );
         }
     }
 
@@ -32,121 +67,25 @@ pub mod commands {
         pub fn new(direction: Direction) -> MoveCommand {
             MoveCommand { direction }
         }
-
-        fn check_for_wumpus(
-            &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
-            player_cavern: &String,
-            wumpus_cavern: &String,
-        ) {
-            if wumpus_cavern == player_cavern {
-                message_receiver.player_moves_to_wumpus();
-            }
-        }
-
-        fn check_for_pit(
-            &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
-            player_cavern: &String,
-            pit_caverns: &PitCaverns,
-        ) -> Option<u32> {
-            let mut self_damage = None;
-            if pit_caverns.contains(player_cavern) {
-                message_receiver.fell_in_pit();
-                self_damage = Some(4);
-            }
-            self_damage
-        }
-
-        fn randomly_transport_player(&self, caverns: &Caverns, player_cavern: &String) -> String {
-            let mut transport_choices = HashSet::new();
-            transport_choices.extend(caverns);
-            transport_choices.remove(&player_cavern);
-            let n_choices = transport_choices.len();
-            let choice = rand::thread_rng().gen_range(0..n_choices);
-            Vec::from_iter(transport_choices)[choice].to_string()
-        }
-
-        fn check_for_bats(
-            &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
-            caverns: &Caverns,
-            player_cavern: &String,
-            bat_caverns: &BatCaverns,
-        ) -> Option<String> {
-            if bat_caverns.contains(player_cavern) {
-                message_receiver.bats_transport();
-                let new_player_cavern = self.randomly_transport_player(caverns, player_cavern);
-                Some(new_player_cavern)
-            } else {
-                None
-            }
-        }
-
-        fn get_arrows_in_cavern(&self, arrows_in: &ArrowsIn, cavern: &String) -> u32 {
-            match arrows_in.get(cavern) {
-                Some(&number) => number,
-                None => 0,
-            }
-        }
-
-        fn check_for_arrows(
-            &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
-            player_cavern: &String,
-            quiver: u32,
-            arrows_in: &ArrowsIn,
-        ) -> (Option<u32>, Option<ArrowsIn>) {
-            let arrows_found = self.get_arrows_in_cavern(arrows_in, player_cavern);
-            let mut new_quiver = None;
-            if arrows_found > 0 {
-                message_receiver.arrows_found(arrows_found);
-                new_quiver = Some(quiver + arrows_found);
-            }
-            let update_arrows_in = Some(HashMap::from([(player_cavern.to_string(), 0)]));
-            (new_quiver, update_arrows_in)
-        }
     }
     impl Command for MoveCommand {
         fn process_command(
             &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
+            message_receiver: &dyn HtwMessageReceiver,
             connections: &Connections,
-            caverns: &Caverns,
-            player_cavern: &String,
-            wumpus_cavern: &String,
-            pit_caverns: &PitCaverns,
-            bat_caverns: &BatCaverns,
-            quiver: u32,
-            arrows_in: &ArrowsIn,
-        ) -> (Option<String>, Option<u32>, Option<ArrowsIn>, Option<u32>) {
+            player_cavern: &str,
+            _wumpus_cavern: &str,
+            _quiver: u32,
+            _arrows_in: &ArrowsIn,
+        ) -> CommandResult {
             match connections.find_destination(player_cavern, &self.direction) {
-                Some(s) => {
-                    let new_player_cavern = s;
-                    self.check_for_wumpus(message_receiver, &new_player_cavern, wumpus_cavern);
-                    let self_damage =
-                        self.check_for_pit(message_receiver, &new_player_cavern, pit_caverns);
-                    let new_player_cavern = match self.check_for_bats(
-                        message_receiver,
-                        caverns,
-                        &new_player_cavern,
-                        bat_caverns,
-                    ) {
-                        Some(s) => s,
-                        None => new_player_cavern,
-                    };
-                    let (new_quiver, update_arrows_in) = self.check_for_arrows(
-                        message_receiver,
-                        &new_player_cavern,
-                        quiver,
-                        arrows_in,
-                    );
-                    let new_player_cavern = Some(new_player_cavern);
-                    return (new_player_cavern, new_quiver, update_arrows_in, self_damage);
-                }
+                Some(new_player_cavern) => CommandResult {
+                    new_player_cavern: Some(new_player_cavern),
+                    ..Default::default()
+                },
                 None => {
                     message_receiver.no_passage();
-                    return (None, None, None, None);
+                    CommandResult::default()
                 }
             }
         }
@@ -155,135 +94,46 @@ pub mod commands {
     #[cfg(test)]
     mod tests_for_move_command {
         use super::*;
-        use crate::english_message_receiver::english_htw_message_receiver::EnglishHtwMessageReceiver;
+        use crate::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+        use alloc::vec;
 
-        fn set_up_command() -> MoveCommand {
-            let direction = Direction::North;
-            MoveCommand::new(direction)
-        }
-
-        fn set_up() -> (
-            Box<dyn HtwMessageReceiver>,
-            Caverns,
-            BatCaverns,
-            PitCaverns,
-            ArrowsIn,
-            u32,
-            MoveCommand,
-        ) {
-            let message_receiver = Box::new(EnglishHtwMessageReceiver {});
-            let caverns = HashSet::from([
-                String::from("cavern"),
-                String::from("cavern_w"),
-                String::from("cavern_e"),
-                String::from("cavern_n"),
-                String::from("cavern_s"),
-                String::from("cavern_nn"),
+        fn set_up() -> (HtwMessageLog, Connections, MoveCommand) {
+            let message_receiver = HtwMessageLog::new();
+            let connections = Connections::new(vec![
+                Connection::new("cavern", "cavern_n", &Direction::North),
+                Connection::new("cavern_n", "cavern", &Direction::South),
             ]);
-            let bat_caverns = HashSet::from([String::from("cavern_n")]);
-            let pit_caverns = HashSet::from([String::from("cavern_n"), String::from("cavern_nn")]);
-            let arrows_in = HashMap::from([(String::from("cavern_n"), 5)]);
-            let quiver = 5;
-            let command = set_up_command();
-            (
-                message_receiver,
-                caverns,
-                bat_caverns,
-                pit_caverns,
-                arrows_in,
-                quiver,
-                command,
-            )
+            let command = MoveCommand::new(Direction::North);
+            (message_receiver, connections, command)
         }
 
         #[test]
-        fn test_check_for_pit_no_pit() {
-            let (message_receiver, _, _, pit_caverns, _, _, command) = set_up();
-            let player_cavern = String::from("cavern");
-            assert_eq!(
-                None,
-                command.check_for_pit(&message_receiver, &player_cavern, &pit_caverns)
-            );
-        }
-
-        #[test]
-        fn test_check_for_pit_pit_exists() {
-            let (message_receiver, _, _, pit_caverns, _, _, command) = set_up();
-            let player_cavern = String::from("cavern_n");
-            assert_eq!(
-                Some(4),
-                command.check_for_pit(&message_receiver, &player_cavern, &pit_caverns)
-            );
-        }
-
-        #[test]
-        fn test_randomly_transport_player() {
-            let player_cavern = String::from("cavern");
-            let (_, caverns, _, _, _, _, command) = set_up();
-            let result = command.randomly_transport_player(&caverns, &player_cavern);
-            assert_ne!(String::from("cavern"), result);
-        }
-
-        #[test]
-        fn test_check_for_bats_no_bats() {
-            let (message_receiver, caverns, bat_caverns, _, _, _, command) = set_up();
-            let player_cavern = String::from("cavern");
-            let result =
-                command.check_for_bats(&message_receiver, &caverns, &player_cavern, &bat_caverns);
-            assert_eq!(None, result);
-        }
-
-        #[test]
-        fn test_check_for_bats_bat_exists() {
-            let (message_receiver, caverns, bat_caverns, _, _, _, command) = set_up();
-            let player_cavern = String::from("cavern_n");
-            let result =
-                command.check_for_bats(&message_receiver, &caverns, &player_cavern, &bat_caverns);
-            assert_ne!(None, result);
-            assert_ne!(Some(String::from("cavern_n")), result);
-        }
-
-        #[test]
-        fn test_get_arrows_in_cavern_no_arrows() {
-            let (_, _, _, _, arrows_in, _, command) = set_up();
-            let cavern = String::from("cavern");
-            let result = command.get_arrows_in_cavern(&arrows_in, &cavern);
-            assert_eq!(0, result);
-        }
-
-        #[test]
-        fn test_get_arrows_in_cavern_5_arrows() {
-            let (_, _, _, _, arrows_in, _, command) = set_up();
-            let cavern = String::from("cavern_n");
-            let result = command.get_arrows_in_cavern(&arrows_in, &cavern);
-            assert_eq!(5, result);
-        }
-
-        #[test]
-        fn test_check_for_arrows_no_arrows() {
-            let player_cavern = String::from("cavern");
-            let (message_receiver, _, _, _, arrows_in, quiver, command) = set_up();
-            let result =
-                command.check_for_arrows(&message_receiver, &player_cavern, quiver, &arrows_in);
-            assert_eq!(
-                (None, Some(HashMap::from([(String::from("cavern"), 0)]))),
-                result
+        fn test_process_command_moves_player() {
+            let (message_receiver, connections, command) = set_up();
+            let result = command.process_command(
+                &message_receiver,
+                &connections,
+                "cavern",
+                "cavern_w",
+                5,
+                &BTreeMap::new(),
             );
+            assert_eq!(Some(String::from("cavern_n")), result.new_player_cavern);
         }
 
         #[test]
-        fn test_check_for_arrows_5_arrows() {
-            let player_cavern = String::from("cavern_n");
-            let (message_receiver, _, _, _, arrows_in, quiver, command) = set_up();
-            let result =
-                command.check_for_arrows(&message_receiver, &player_cavern, quiver, &arrows_in);
-            assert_eq!(
-                (
-                    Some(10),
-                    Some(HashMap::from([(String::from("cavern_n"), 0)]))
-                ),
-                result
+        fn test_process_command_no_passage() {
+            let (message_receiver, connections, command) = set_up();
+            let result = command.process_command(
+                &message_receiver,
+                &connections,
+                "cavern_n",
+                "cavern_w",
+                5,
+                &BTreeMap::new(),
             );
+            assert_eq!(CommandResult::default(), result);
+            assert_eq!(Some(HtwMessage::NoPassage), message_receiver.last_message());
         }
     }
 
@@ -308,26 +158,22 @@ pub mod commands {
             arrow_cavern: &String,
         ) -> Option<ArrowsIn> {
             let arrows = self.get_arrows_in_cavern(arrows_in, arrow_cavern);
-            let update_arrows_in = Some(HashMap::from([(arrow_cavern.to_string(), arrows + 1)]));
-            update_arrows_in
+            Some(BTreeMap::from([(arrow_cavern.to_string(), arrows + 1)]))
         }
     }
     impl Command for ShootCommand {
         fn process_command(
             &self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
+            message_receiver: &dyn HtwMessageReceiver,
             connections: &Connections,
-            _caverns: &Caverns,
-            player_cavern: &String,
-            wumpus_cavern: &String,
-            _pit_caverns: &PitCaverns,
-            _bat_caverns: &BatCaverns,
+            player_cavern: &str,
+            wumpus_cavern: &str,
             quiver: u32,
             arrows_in: &ArrowsIn,
-        ) -> (Option<String>, Option<u32>, Option<ArrowsIn>, Option<u32>) {
+        ) -> CommandResult {
             if quiver == 0 {
                 message_receiver.no_arrows();
-                return (None, None, None, None);
+                CommandResult::default()
             } else {
                 message_receiver.arrow_shot();
                 let new_quiver = Some(quiver - 1);
@@ -340,11 +186,19 @@ pub mod commands {
                     wumpus_cavern,
                 );
                 if arrow_tracker.arrow_hit_something() {
-                    return (None, None, None, self_damage);
+                    CommandResult {
+                        self_damage,
+                        killed_wumpus: arrow_tracker.arrow_killed_wumpus(),
+                        ..Default::default()
+                    }
                 } else {
                     let update_arrows_in = self
                         .increment_arrows_in_cavern(arrows_in, &arrow_tracker.get_arrow_cavern());
-                    return (None, new_quiver, update_arrows_in, None);
+                    CommandResult {
+                        new_quiver,
+                        update_arrows_in,
+                        ..Default::default()
+                    }
                 }
             }
         }
@@ -356,7 +210,7 @@ pub mod commands {
         fn set_up() -> (ShootCommand, ArrowsIn) {
             let direction = Direction::North;
             let command = ShootCommand::new(direction);
-            let arrows_in = HashMap::from([(String::from("cavern_n"), 5)]);
+            let arrows_in = BTreeMap::from([(String::from("cavern_n"), 5)]);
             (command, arrows_in)
         }
 
@@ -381,18 +235,23 @@ pub mod commands {
             let (command, arrows_in) = set_up();
             let arrow_cavern = String::from("cavern_n");
             let result = command.increment_arrows_in_cavern(&arrows_in, &arrow_cavern);
-            assert_eq!(Some(HashMap::from([(String::from("cavern_n"), 6)])), result);
+            assert_eq!(
+                Some(BTreeMap::from([(String::from("cavern_n"), 6)])),
+                result
+            );
         }
     }
 
     struct ArrowTracker {
         hit_something: bool,
+        killed_wumpus: bool,
         arrow_cavern: String,
     }
     impl ArrowTracker {
         fn new(starting_cavern: String) -> ArrowTracker {
             ArrowTracker {
                 hit_something: false,
+                killed_wumpus: false,
                 arrow_cavern: starting_cavern,
             }
         }
@@ -401,6 +260,10 @@ pub mod commands {
             self.hit_something
         }
 
+        fn arrow_killed_wumpus(&self) -> bool {
+            self.killed_wumpus
+        }
+
         fn get_arrow_cavern(&self) -> String {
             self.arrow_cavern.to_string()
         }
@@ -416,50 +279,52 @@ pub mod commands {
                     return Some(c.to().to_string());
                 }
             }
-            return None;
+            None
         }
 
         fn shoot_self_in_back(
             &mut self,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
-        ) -> Option<u32> {
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> Option<(u32, DeathCause)> {
             message_receiver.player_shoots_self_in_back();
             self.hit_something = true;
-            let self_damage = Some(3);
-            self_damage
+            Some((3, DeathCause::ShotSelf))
         }
 
-        fn shoot_wumpus(&mut self, message_receiver: &Box<dyn HtwMessageReceiver>) {
+        fn shoot_wumpus(&mut self, message_receiver: &dyn HtwMessageReceiver) {
             message_receiver.player_kills_wumpus();
             self.hit_something = true;
+            self.killed_wumpus = true;
         }
 
-        fn shoot_wall(&mut self, message_receiver: &Box<dyn HtwMessageReceiver>) -> Option<u32> {
+        fn shoot_wall(
+            &mut self,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> Option<(u32, DeathCause)> {
             message_receiver.player_shoots_wall();
             self.hit_something = true;
-            let self_damage = Some(3);
-            self_damage
+            Some((3, DeathCause::ShotWall))
         }
 
         fn track_arrow(
             &mut self,
             direction: &Direction,
-            message_receiver: &Box<dyn HtwMessageReceiver>,
+            message_receiver: &dyn HtwMessageReceiver,
             connections: &Vec<Connection>,
-            player_cavern: &String,
-            wumpus_cavern: &String,
-        ) -> Option<u32> {
+            player_cavern: &str,
+            wumpus_cavern: &str,
+        ) -> Option<(u32, DeathCause)> {
             let mut count = 0;
             while let Some(c) =
                 self.next_cavern(self.arrow_cavern.to_string(), direction, connections)
             {
                 count += 1;
                 self.arrow_cavern = c;
-                if &self.arrow_cavern == player_cavern {
+                if self.arrow_cavern == player_cavern {
                     let self_damage = self.shoot_self_in_back(message_receiver);
                     return self_damage;
                 };
-                if &self.arrow_cavern == wumpus_cavern {
+                if self.arrow_cavern == wumpus_cavern {
                     self.shoot_wumpus(message_receiver);
                     return None;
                 };
@@ -468,7 +333,7 @@ pub mod commands {
                 };
             }
             // when there is no connecting cavern in the shooting direction, the arrow hits wall.
-            if &self.arrow_cavern == player_cavern {
+            if self.arrow_cavern == player_cavern {
                 let self_damage = self.shoot_wall(message_receiver);
                 return self_damage;
             }
@@ -480,20 +345,16 @@ pub mod commands {
     mod tests_for_arrow_tracker {
         use super::*;
         use crate::connection::connection::Connection;
-        use crate::english_message_receiver::english_htw_message_receiver::EnglishHtwMessageReceiver;
+        use crate::htw_message::htw_message::HtwMessageLog;
+        use alloc::vec;
 
         fn set_up_tracker() -> ArrowTracker {
             ArrowTracker::new(String::from("cavern"))
         }
 
-        fn set_up() -> (
-            ArrowTracker,
-            Box<dyn HtwMessageReceiver>,
-            Direction,
-            Vec<Connection>,
-        ) {
+        fn set_up() -> (ArrowTracker, HtwMessageLog, Direction, Vec<Connection>) {
             let tracker = set_up_tracker();
-            let message_receiver = Box::new(EnglishHtwMessageReceiver {});
+            let message_receiver = HtwMessageLog::new();
             let direction = Direction::North;
             let connections = vec![
                 Connection::new("cavern", "cavern_n", &Direction::North),
@@ -511,7 +372,7 @@ pub mod commands {
         #[test]
         fn test_new() {
             let result = ArrowTracker::new(String::from("cavern"));
-            assert_eq!(result.hit_something, false);
+            assert!(!result.hit_something);
             assert_eq!(result.arrow_cavern, "cavern");
         }
 
@@ -548,7 +409,7 @@ pub mod commands {
         fn test_shoot_self_in_back() {
             let (mut tracker, message_receiver, _, _) = set_up();
             let result = tracker.shoot_self_in_back(&message_receiver);
-            assert_eq!(result, Some(3));
+            assert_eq!(result, Some((3, DeathCause::ShotSelf)));
         }
 
         #[test]
@@ -556,7 +417,7 @@ pub mod commands {
             let (mut tracker, message_receiver, _, _) = set_up();
             let self_damage = tracker.shoot_wall(&message_receiver);
             assert!(tracker.arrow_hit_something());
-            assert_eq!(Some(3), self_damage);
+            assert_eq!(Some((3, DeathCause::ShotWall)), self_damage);
         }
 
         #[test]
@@ -586,7 +447,7 @@ pub mod commands {
                 &player_cavern,
                 &wumpus_cavern,
             );
-            assert_eq!(Some(3), result);
+            assert_eq!(Some((3, DeathCause::ShotSelf)), result);
             assert!(tracker.arrow_hit_something());
         }
 
@@ -603,7 +464,7 @@ pub mod commands {
                 &player_cavern,
                 &wumpus_cavern,
             );
-            assert_eq!(Some(3), result);
+            assert_eq!(Some((3, DeathCause::ShotWall)), result);
             assert!(tracker.arrow_hit_something());
         }
 
@@ -623,5 +484,22 @@ pub mod commands {
             assert_eq!(None, result);
             assert!(!tracker.arrow_hit_something());
         }
+
+        #[test]
+        fn test_track_arrow_kills_wumpus() {
+            let (mut tracker, message_receiver, direction, connections) = set_up();
+            let player_cavern = String::from("cavern");
+            let wumpus_cavern = String::from("cavern_nn");
+            let result = tracker.track_arrow(
+                &direction,
+                &message_receiver,
+                &connections,
+                &player_cavern,
+                &wumpus_cavern,
+            );
+            assert_eq!(None, result);
+            assert!(tracker.arrow_hit_something());
+            assert!(tracker.arrow_killed_wumpus());
+        }
     }
 }
@@ -1,5 +1,6 @@
 pub mod connection {
     use crate::direction::direction::Direction;
+    use alloc::string::String;
 
     #[derive(Debug, PartialEq)]
     pub struct Connection {
@@ -0,0 +1,648 @@
+pub mod connections {
+    use crate::connection::connection::Connection;
+    use crate::htw_error::htw_error::{HtwError, MapViolation};
+    use crate::htw_game::htw_game::Caverns;
+    use crate::Direction;
+    use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, RngCore};
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MapGenerator {
+        // links each cavern to random others.
+        Random,
+        // lays the caverns out on a square grid, linked to their neighbours.
+        Grid,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Connections {
+        pub connections: Vec<Connection>,
+    }
+
+    impl Connections {
+        pub fn new(connections: Vec<Connection>) -> Connections {
+            Connections { connections }
+        }
+
+        pub fn generate(
+            generator: MapGenerator,
+            caverns: &Caverns,
+            rng: &mut dyn RngCore,
+        ) -> Result<Connections, HtwError> {
+            let mut connections = Connections::new(vec![]);
+            match generator {
+                MapGenerator::Random => {
+                    connections.connect_caverns(caverns, rng)?;
+                    connections.connect_cut_off_caverns(caverns, rng);
+                }
+                MapGenerator::Grid => connections.connect_caverns_in_grid(caverns, rng),
+            }
+            connections.validate(caverns)?;
+            Ok(connections)
+        }
+
+        // random passages may leave some caverns cut off. they are linked to caverns that can
+        // be reached, one at a time, as long as a pair of them has a direction free.
+        fn connect_cut_off_caverns(&mut self, caverns: &Caverns, rng: &mut dyn RngCore) {
+            loop {
+                let reached = self.reachable(caverns);
+                let (mut others, cut_off): (Vec<&String>, Vec<&String>) = sorted(caverns)
+                    .into_iter()
+                    .partition(|c| reached.contains(*c));
+                if cut_off.is_empty() {
+                    return;
+                }
+                others.shuffle(rng);
+                let n_connections = self.connections.len();
+                'search: for cavern in cut_off {
+                    for other in &others {
+                        for direction in &DIRECTIONS {
+                            self.check_and_connect_cavern(cavern, other, direction);
+                            if self.connections.len() > n_connections {
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+                if self.connections.len() == n_connections {
+                    return;
+                }
+            }
+        }
+
+        // the caverns that can be reached from the first one.
+        fn reachable(&self, caverns: &Caverns) -> BTreeSet<String> {
+            let mut passages: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            for c in &self.connections {
+                passages.entry(c.from()).or_default().push(c.to());
+            }
+            let mut reached = BTreeSet::new();
+            let mut queue: VecDeque<&str> = sorted(caverns)
+                .first()
+                .map(|c| c.as_str())
+                .into_iter()
+                .collect();
+            while let Some(cavern) = queue.pop_front() {
+                if reached.insert(String::from(cavern)) {
+                    queue.extend(passages.get(cavern).into_iter().flatten());
+                }
+            }
+            reached
+        }
+
+        // checks that every passage leads back the opposite way, that a cavern has at most one
+        // passage in each direction and to each other cavern, that passages join known caverns
+        // and that every cavern can be reached.
+        pub fn validate(&self, caverns: &Caverns) -> Result<(), HtwError> {
+            let violations = self.violations(caverns);
+            if violations.is_empty() {
+                Ok(())
+            } else {
+                Err(HtwError::InvalidMap(violations))
+            }
+        }
+
+        pub fn violations(&self, caverns: &Caverns) -> Vec<MapViolation> {
+            let mut violations = vec![];
+            let mut push = |violation: MapViolation| {
+                if !violations.contains(&violation) {
+                    violations.push(violation);
+                }
+            };
+            // the passages out of each cavern, by the cavern they lead to.
+            let mut passages: BTreeMap<&str, Vec<&Connection>> = BTreeMap::new();
+            for c in &self.connections {
+                passages.entry(c.from()).or_default().push(c);
+            }
+            let mut directions = BTreeSet::new();
+            let mut links = BTreeSet::new();
+            for c in &self.connections {
+                for cavern in [c.from(), c.to()] {
+                    if !caverns.contains(cavern) {
+                        push(MapViolation::UnknownCavern(String::from(cavern)));
+                    }
+                }
+                if !directions.insert((c.from(), *c.direction())) {
+                    push(MapViolation::DuplicateDirection {
+                        cavern: String::from(c.from()),
+                        direction: *c.direction(),
+                    });
+                }
+                if c.from() == c.to() {
+                    push(MapViolation::SelfLoop {
+                        cavern: String::from(c.from()),
+                        direction: *c.direction(),
+                    });
+                    continue;
+                }
+                if !links.insert((c.from(), c.to())) {
+                    push(MapViolation::DoubleLink {
+                        from: String::from(c.from()),
+                        to: String::from(c.to()),
+                    });
+                }
+                let reverses: Vec<&&Connection> = passages
+                    .get(c.to())
+                    .into_iter()
+                    .flatten()
+                    .filter(|r| r.to() == c.from())
+                    .collect();
+                match reverses
+                    .iter()
+                    .find(|r| *r.direction() == c.direction().opposite())
+                {
+                    Some(_) => {}
+                    None if reverses.is_empty() => push(MapViolation::MissingReverse {
+                        from: String::from(c.from()),
+                        to: String::from(c.to()),
+                        direction: *c.direction(),
+                    }),
+                    None => push(MapViolation::WrongReverseDirection {
+                        from: String::from(c.from()),
+                        to: String::from(c.to()),
+                        direction: *c.direction(),
+                        reverse: *reverses[0].direction(),
+                    }),
+                }
+            }
+
+            let reached = self.reachable(caverns);
+            for cavern in sorted(caverns) {
+                if !reached.contains(cavern) {
+                    push(MapViolation::Unreachable(cavern.clone()));
+                }
+            }
+            violations
+        }
+
+        pub fn report_nearby(&self, cavern: &str, target_caverns: &Caverns) -> bool {
+            let mut result = false;
+            for c in &self.connections {
+                result = result
+                    || (c.from() == cavern && target_caverns.contains(&String::from(c.to())));
+            }
+            result
+        }
+
+        pub fn find_destination(&self, cavern: &str, direction: &Direction) -> Option<String> {
+            for c in &self.connections {
+                if c.from() == cavern && c.direction() == direction {
+                    return Some(c.to().to_string());
+                }
+            }
+            None
+        }
+
+        pub fn report_available_directions(&self, cavern: &str) -> Vec<Direction> {
+            let mut result = Vec::new();
+            for c in &self.connections {
+                if c.from() == cavern {
+                    result.push(*c.direction());
+                }
+            }
+            result
+        }
+
+        fn is_connectable_cavern(&self, this: &str, other: &str, direction: &Direction) -> bool {
+            for c in &self.connections {
+                if c.from() == this {
+                    let unavailable_cavern = c.to() == other;
+                    let unavailable_direction = c.direction() == direction;
+                    if unavailable_cavern || unavailable_direction {
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+
+        fn check_and_connect_cavern(&mut self, from: &str, to: &str, direction: &Direction) {
+            if self.is_connectable_cavern(from, to, direction)
+                && self.is_connectable_cavern(to, from, &direction.opposite())
+            {
+                let connection = Connection::new(from, to, direction);
+                self.connections.push(connection);
+                let connection = Connection::new(to, from, &direction.opposite());
+                self.connections.push(connection);
+            }
+        }
+
+        pub fn connect_caverns(
+            &mut self,
+            caverns: &Caverns,
+            rng: &mut dyn RngCore,
+        ) -> Result<(), HtwError> {
+            let sorted_caverns = sorted(caverns);
+            for cavern in &sorted_caverns {
+                for direction in &DIRECTIONS {
+                    if rng.gen_range(0..10) > 2 {
+                        let other = choose_other(cavern, &sorted_caverns, rng)?;
+                        self.check_and_connect_cavern(cavern, &other, direction);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        pub fn connect_caverns_in_grid(&mut self, caverns: &Caverns, rng: &mut dyn RngCore) {
+            let mut grid = sorted(caverns);
+            grid.shuffle(rng);
+            let n = grid.len();
+            let root = n.isqrt();
+            let width = if root * root < n { root + 1 } else { root }.max(1);
+            for i in 0..n {
+                if (i + 1) % width != 0 && i + 1 < n {
+                    self.check_and_connect_cavern(grid[i], grid[i + 1], &Direction::East);
+                }
+                if i + width < n {
+                    self.check_and_connect_cavern(grid[i], grid[i + width], &Direction::South);
+                }
+            }
+        }
+    }
+
+    // caverns in a fixed order, so that a seeded rng always makes the same choices.
+    pub fn sorted(caverns: &Caverns) -> Vec<&String> {
+        let mut vector = Vec::from_iter(caverns);
+        vector.sort();
+        vector
+    }
+
+    pub fn any_cavern(caverns: &Caverns, rng: &mut dyn RngCore) -> Result<String, HtwError> {
+        let vector = sorted(caverns);
+        let n = vector.len();
+        if n == 0 {
+            return Err(HtwError::NoCaverns);
+        }
+        let choice = rng.gen_range(0..n);
+        Ok(vector[choice].to_string())
+    }
+
+    pub fn any_other(
+        cavern: &str,
+        caverns: &Caverns,
+        rng: &mut dyn RngCore,
+    ) -> Result<String, HtwError> {
+        choose_other(cavern, &sorted(caverns), rng)
+    }
+
+    // like `any_other`, without sorting the caverns again for every choice.
+    fn choose_other(
+        cavern: &str,
+        sorted_caverns: &[&String],
+        rng: &mut dyn RngCore,
+    ) -> Result<String, HtwError> {
+        let others: Vec<&&String> = sorted_caverns.iter().filter(|c| **c != cavern).collect();
+        if others.is_empty() {
+            return Err(HtwError::NoOtherCavern(String::from(cavern)));
+        }
+        let choice = rng.gen_range(0..others.len());
+        Ok(others[choice].to_string())
+    }
+
+    #[cfg(test)]
+    mod tests_for_connections {
+        use super::*;
+        use alloc::collections::BTreeSet;
+        use alloc::format;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        fn type_of<T>(_: &T) -> &str {
+            core::any::type_name::<T>()
+        }
+
+        fn set_up() -> Connections {
+            let connections = vec![
+                Connection::new("cavern", "cavern_w", &Direction::West),
+                Connection::new("cavern_w", "cavern", &Direction::East),
+                Connection::new("cavern", "cavern_e", &Direction::East),
+                Connection::new("cavern_e", "cavern", &Direction::West),
+                Connection::new("cavern", "cavern_n", &Direction::North),
+                Connection::new("cavern_n", "cavern", &Direction::South),
+                Connection::new("cavern", "cavern_s", &Direction::South),
+                Connection::new("cavern_s", "cavern", &Direction::North),
+                Connection::new("cavern_n", "cavern_nn", &Direction::North),
+                Connection::new("cavern_nn", "cavern_n", &Direction::South),
+            ];
+            Connections::new(connections)
+        }
+
+        #[test]
+        fn test_report_nearby() {
+            let connections = set_up();
+            let cavern = String::from("cavern");
+            let target_caverns = BTreeSet::from([String::from("cavern_e")]);
+            assert!(connections.report_nearby(&cavern, &target_caverns));
+
+            let target_caverns = BTreeSet::from([String::from("cavern_nn")]);
+            assert!(!connections.report_nearby(&cavern, &target_caverns));
+        }
+
+        #[test]
+        fn test_find_destination() {
+            let connections = set_up();
+            let cavern = String::from("cavern");
+            let result = connections.find_destination(&cavern, &Direction::South);
+            assert_eq!(result, Some(String::from("cavern_s")));
+
+            let cavern = String::from("cavern_s");
+            let result = connections.find_destination(&cavern, &Direction::South);
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn test_report_available_directions() {
+            let connections = set_up();
+            let cavern = String::from("cavern_n");
+            let result = connections.report_available_directions(&cavern);
+            // TODO: change this so order does not matter.
+            assert_eq!(Vec::from([Direction::South, Direction::North]), result);
+        }
+
+        #[test]
+        fn test_any_cavern() {
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            let result = any_cavern(&caverns, &mut rand::thread_rng()).unwrap();
+            assert_eq!("alloc::string::String", type_of(&result));
+            assert!(caverns.contains(&result));
+        }
+
+        #[test]
+        fn test_any_cavern_no_caverns() {
+            assert_eq!(
+                Err(HtwError::NoCaverns),
+                any_cavern(&BTreeSet::new(), &mut rand::thread_rng())
+            );
+        }
+
+        #[test]
+        fn test_any_other() {
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            assert_ne!(
+                "cavern",
+                any_other("cavern", &caverns, &mut rand::thread_rng()).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_any_other_single_cavern() {
+            let caverns = BTreeSet::from([String::from("cavern")]);
+            assert_eq!(
+                Err(HtwError::NoOtherCavern(String::from("cavern"))),
+                any_other("cavern", &caverns, &mut rand::thread_rng())
+            );
+        }
+
+        #[test]
+        fn test_connections_equality() {
+            let connections1 = set_up();
+            let connections2 = set_up();
+            assert_eq!(connections1, connections2);
+        }
+
+        #[test]
+        fn test_is_connectable_cavern() {
+            let connections = Connections::new(vec![Connection::new(
+                "cavern",
+                "cavern_n",
+                &Direction::North,
+            )]);
+            let this = "cavern";
+            let available_cavern = "cavern_w";
+            let available_direction = Direction::West;
+            let unavailable_cavern = "cavern_n";
+            let unavailable_direction = Direction::North;
+            // can connect to cavern_w but not to cavern_n
+            assert!(connections.is_connectable_cavern(
+                this,
+                available_cavern,
+                &available_direction
+            ));
+            assert!(!connections.is_connectable_cavern(
+                this,
+                unavailable_cavern,
+                &available_direction
+            ));
+
+            // cannot connect to Direction::North anymore
+            assert!(!connections.is_connectable_cavern(
+                this,
+                unavailable_cavern,
+                &unavailable_direction
+            ));
+            assert!(!connections.is_connectable_cavern(
+                this,
+                available_cavern,
+                &unavailable_direction
+            ));
+        }
+
+        #[test]
+        fn test_check_and_connect_cavern() {
+            let mut connections = Connections::new(vec![]);
+            connections.check_and_connect_cavern("cavern", "cavern_n", &Direction::North);
+            assert_eq!(
+                connections.connections[0],
+                Connection::new("cavern", "cavern_n", &Direction::North)
+            );
+            assert_eq!(
+                connections.connections[1],
+                Connection::new("cavern_n", "cavern", &Direction::South)
+            );
+            // cannot connect to cavern_n anymore
+            connections.check_and_connect_cavern("cavern", "cavern_n", &Direction::West);
+            // cannot connect to North anymore
+            connections.check_and_connect_cavern("cavern", "another_cavern", &Direction::North);
+            assert_eq!(connections.connections.len(), 2);
+        }
+
+        #[test]
+        fn test_connect_caverns() {
+            let mut connections = Connections::new(vec![]);
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            connections
+                .connect_caverns(&caverns, &mut rand::thread_rng())
+                .unwrap();
+            assert_ne!(0, connections.connections.len());
+
+            for cavern in caverns {
+                let mut connected_caverns = vec![];
+                let mut connected_directions = vec![];
+                for c in &connections.connections {
+                    if c.from() == cavern {
+                        connected_caverns.push(String::from(c.to()));
+                        connected_directions.push(String::from(c.direction().name()));
+                    }
+                }
+                let unique_connected_caverns: BTreeSet<&String> =
+                    connected_caverns.iter().collect();
+                let unique_connected_directions: BTreeSet<&String> =
+                    connected_directions.iter().collect();
+                // assert that cavernA is not connected to cavernB in multiple directions
+                assert!(connected_caverns.len() == unique_connected_caverns.len());
+                // assert that a cavern does not have multiple connections in one direction
+                assert!(connected_directions.len() == unique_connected_directions.len());
+            }
+        }
+
+        #[test]
+        fn test_connect_caverns_with_seed() {
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            let mut connections1 = Connections::new(vec![]);
+            connections1
+                .connect_caverns(&caverns, &mut StdRng::seed_from_u64(7))
+                .unwrap();
+            let mut connections2 = Connections::new(vec![]);
+            connections2
+                .connect_caverns(&caverns, &mut StdRng::seed_from_u64(7))
+                .unwrap();
+            assert_eq!(connections1, connections2);
+        }
+
+        #[test]
+        fn test_validate() {
+            let caverns: Caverns = [
+                "cavern",
+                "cavern_w",
+                "cavern_e",
+                "cavern_n",
+                "cavern_s",
+                "cavern_nn",
+            ]
+            .iter()
+            .map(|c| String::from(*c))
+            .collect();
+            assert_eq!(Ok(()), set_up().validate(&caverns));
+        }
+
+        #[test]
+        fn test_validate_violations() {
+            let connections = Connections::new(vec![
+                Connection::new("a", "b", &Direction::North),
+                Connection::new("b", "a", &Direction::East),
+                Connection::new("a", "c", &Direction::North),
+                Connection::new("c", "a", &Direction::South),
+                Connection::new("a", "c", &Direction::West),
+                Connection::new("c", "a", &Direction::East),
+                Connection::new("a", "a", &Direction::South),
+                Connection::new("c", "x", &Direction::North),
+            ]);
+            let caverns: Caverns = ["a", "b", "c", "d"]
+                .iter()
+                .map(|c| String::from(*c))
+                .collect();
+            let north = Direction::North;
+            assert_eq!(
+                vec![
+                    MapViolation::WrongReverseDirection {
+                        from: String::from("a"),
+                        to: String::from("b"),
+                        direction: north,
+                        reverse: Direction::East,
+                    },
+                    MapViolation::WrongReverseDirection {
+                        from: String::from("b"),
+                        to: String::from("a"),
+                        direction: Direction::East,
+                        reverse: north,
+                    },
+                    MapViolation::DuplicateDirection {
+                        cavern: String::from("a"),
+                        direction: north,
+                    },
+                    MapViolation::DoubleLink {
+                        from: String::from("a"),
+                        to: String::from("c"),
+                    },
+                    MapViolation::DoubleLink {
+                        from: String::from("c"),
+                        to: String::from("a"),
+                    },
+                    MapViolation::SelfLoop {
+                        cavern: String::from("a"),
+                        direction: Direction::South,
+                    },
+                    MapViolation::UnknownCavern(String::from("x")),
+                    MapViolation::MissingReverse {
+                        from: String::from("c"),
+                        to: String::from("x"),
+                        direction: north,
+                    },
+                    MapViolation::Unreachable(String::from("d")),
+                ],
+                connections.violations(&caverns)
+            );
+        }
+
+        #[test]
+        fn test_generate_valid_maps() {
+            for n_caverns in [11, 15, 30] {
+                let caverns: Caverns = (0..n_caverns).map(|i| format!("cavern_{}", i)).collect();
+                for seed in 0..40 {
+                    for generator in [MapGenerator::Random, MapGenerator::Grid] {
+                        let connections = Connections::generate(
+                            generator,
+                            &caverns,
+                            &mut StdRng::seed_from_u64(seed),
+                        )
+                        .unwrap();
+                        assert_eq!(Ok(()), connections.validate(&caverns));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_connect_caverns_in_grid() {
+            let caverns: Caverns = (0..7).map(|i| format!("cavern_{}", i)).collect();
+            let connections =
+                Connections::generate(MapGenerator::Grid, &caverns, &mut rand::thread_rng())
+                    .unwrap();
+            // a 3x3 grid holding 7 caverns has 8 passages, each listed both ways.
+            assert_eq!(16, connections.connections.len());
+            for cavern in &caverns {
+                assert!(!connections.report_available_directions(cavern).is_empty());
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+pub mod difficulty {
+    use crate::game_config::game_config::GameConfig;
+    use core::ops::RangeInclusive;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Difficulty {
+        Easy,
+        Normal,
+        Hard,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Preset {
+        pub caverns: RangeInclusive<usize>,
+        pub bats: usize,
+        pub pits: usize,
+        pub arrows: u32,
+        pub config: GameConfig,
+    }
+
+    impl Difficulty {
+        // accepts the name in any case.
+        pub fn parse(name: &str) -> Option<Difficulty> {
+            match &*name.to_lowercase() {
+                "easy" => Some(Difficulty::Easy),
+                "normal" => Some(Difficulty::Normal),
+                "hard" => Some(Difficulty::Hard),
+                _ => None,
+            }
+        }
+
+        pub fn preset(&self) -> Preset {
+            match self {
+                Difficulty::Easy => Preset {
+                    caverns: 10..=20,
+                    bats: 2,
+                    pits: 2,
+                    arrows: 7,
+                    config: GameConfig {
+                        max_hit_points: 15,
+                        rest_healing: 2,
+                        safe_bat_drop: true,
+                    },
+                },
+                Difficulty::Normal => Preset {
+                    caverns: 10..=40,
+                    bats: 3,
+                    pits: 3,
+                    arrows: 5,
+                    config: GameConfig::default(),
+                },
+                Difficulty::Hard => Preset {
+                    caverns: 30..=50,
+                    bats: 4,
+                    pits: 5,
+                    arrows: 3,
+                    config: GameConfig {
+                        max_hit_points: 6,
+                        rest_healing: 1,
+                        safe_bat_drop: false,
+                    },
+                },
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_difficulty {
+        use super::*;
+
+        #[test]
+        fn test_normal_preset() {
+            let preset = Difficulty::Normal.preset();
+            assert_eq!(10..=40, preset.caverns);
+            assert_eq!(5, preset.arrows);
+            assert_eq!(GameConfig::default(), preset.config);
+        }
+
+        #[test]
+        fn test_parse() {
+            assert_eq!(Some(Difficulty::Hard), Difficulty::parse("Hard"));
+            assert_eq!(None, Difficulty::parse("nightmare"));
+        }
+
+        #[test]
+        fn test_harder_presets_are_more_dangerous() {
+            let easy = Difficulty::Easy.preset();
+            let hard = Difficulty::Hard.preset();
+            assert!(easy.bats + easy.pits < hard.bats + hard.pits);
+            assert!(easy.config.max_hit_points > hard.config.max_hit_points);
+        }
+    }
+}
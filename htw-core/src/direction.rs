@@ -1,5 +1,5 @@
 pub mod direction {
-    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub enum Direction {
         North,
         South,
@@ -25,6 +25,17 @@ pub mod direction {
                 Direction::West => "West",
             }
         }
+
+        // accepts the full name or its initial, in any case.
+        pub fn parse(name: &str) -> Option<Direction> {
+            match &*name.to_lowercase() {
+                "north" | "n" => Some(Direction::North),
+                "south" | "s" => Some(Direction::South),
+                "east" | "e" => Some(Direction::East),
+                "west" | "w" => Some(Direction::West),
+                _ => None,
+            }
+        }
     }
     #[cfg(test)]
     mod tests_for_direction {
@@ -77,5 +88,12 @@ pub mod direction {
             let west = Direction::West;
             assert_eq!(Direction::East, west.opposite());
         }
+
+        #[test]
+        fn parse_direction() {
+            assert_eq!(Some(Direction::North), Direction::parse("North"));
+            assert_eq!(Some(Direction::East), Direction::parse("e"));
+            assert_eq!(None, Direction::parse("up"));
+        }
     }
 }
@@ -0,0 +1,280 @@
+pub mod game_builder {
+    use crate::connections::connections::Connections;
+    use crate::game_config::game_config::GameConfig;
+    use crate::hazards::hazards::Hazard;
+    use crate::htw_error::htw_error::HtwError;
+    use crate::htw_game::htw_game::{Caverns, HuntTheWumpusGame, TurnHook, TurnPhase};
+    use crate::{HtwMessageReceiver, HuntTheWumpus};
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    pub struct GameBuilder {
+        caverns: Caverns,
+        connections: Option<Connections>,
+        player_cavern: Option<String>,
+        wumpus_cavern: Option<String>,
+        bat_caverns: Vec<String>,
+        pit_caverns: Vec<String>,
+        hazards: Vec<Box<dyn Hazard>>,
+        quiver: u32,
+        message_receiver: Option<Box<dyn HtwMessageReceiver>>,
+        config: GameConfig,
+        turn_hooks: Vec<(TurnPhase, Box<dyn TurnHook>)>,
+        seed: Option<u64>,
+    }
+
+    impl GameBuilder {
+        pub fn new(caverns: Caverns) -> GameBuilder {
+            GameBuilder {
+                caverns,
+                connections: None,
+                player_cavern: None,
+                wumpus_cavern: None,
+                bat_caverns: vec![],
+                pit_caverns: vec![],
+                hazards: vec![],
+                quiver: 0,
+                message_receiver: None,
+                config: GameConfig::default(),
+                turn_hooks: vec![],
+                seed: None,
+            }
+        }
+
+        // without connections, the caverns are connected randomly on build.
+        pub fn connections(mut self, connections: Connections) -> GameBuilder {
+            self.connections = Some(connections);
+            self
+        }
+
+        pub fn player_cavern(mut self, cavern: &str) -> GameBuilder {
+            self.player_cavern = Some(String::from(cavern));
+            self
+        }
+
+        pub fn wumpus_cavern(mut self, cavern: &str) -> GameBuilder {
+            self.wumpus_cavern = Some(String::from(cavern));
+            self
+        }
+
+        pub fn bat_cavern(mut self, cavern: &str) -> GameBuilder {
+            self.bat_caverns.push(String::from(cavern));
+            self
+        }
+
+        pub fn pit_cavern(mut self, cavern: &str) -> GameBuilder {
+            self.pit_caverns.push(String::from(cavern));
+            self
+        }
+
+        pub fn hazard(mut self, hazard: Box<dyn Hazard>) -> GameBuilder {
+            self.hazards.push(hazard);
+            self
+        }
+
+        pub fn quiver(mut self, arrows: u32) -> GameBuilder {
+            self.quiver = arrows;
+            self
+        }
+
+        pub fn message_receiver(
+            mut self,
+            message_receiver: Box<dyn HtwMessageReceiver>,
+        ) -> GameBuilder {
+            self.message_receiver = Some(message_receiver);
+            self
+        }
+
+        pub fn config(mut self, config: GameConfig) -> GameBuilder {
+            self.config = config;
+            self
+        }
+
+        pub fn turn_hook(mut self, phase: TurnPhase, hook: Box<dyn TurnHook>) -> GameBuilder {
+            self.turn_hooks.push((phase, hook));
+            self
+        }
+
+        // without a seed, every game plays out differently. without the entropy feature, e.g.
+        // on wasm32-unknown-unknown, the seed must be set.
+        pub fn seed(mut self, seed: u64) -> GameBuilder {
+            self.seed = Some(seed);
+            self
+        }
+
+        pub fn build(self) -> Result<Box<dyn HuntTheWumpus>, HtwError> {
+            let message_receiver = self
+                .message_receiver
+                .ok_or(HtwError::MessageReceiverNotSet)?;
+            let seed = match self.seed {
+                Some(seed) => seed,
+                None => unseeded()?,
+            };
+            let mut game = HuntTheWumpusGame::new(message_receiver, self.caverns, self.config);
+            game.set_seed(seed);
+            match self.connections {
+                Some(connections) => game.set_connections(connections),
+                None => game.connect_caverns()?,
+            }
+            if let Some(cavern) = &self.player_cavern {
+                game.set_player_cavern(cavern)?;
+            }
+            if let Some(cavern) = &self.wumpus_cavern {
+                game.set_wumpus_cavern(cavern)?;
+            }
+            for cavern in &self.bat_caverns {
+                game.add_bat_cavern(cavern)?;
+            }
+            for cavern in &self.pit_caverns {
+                game.add_pit_cavern(cavern)?;
+            }
+            for hazard in self.hazards {
+                game.add_hazard(hazard)?;
+            }
+            game.set_quiver(self.quiver);
+            for (phase, hook) in self.turn_hooks {
+                game.add_turn_hook(phase, hook);
+            }
+            game.validate()?;
+            game.observe();
+            Ok(Box::new(game))
+        }
+    }
+
+    // a seed from the operating system, which only the entropy feature can ask for.
+    #[cfg(feature = "entropy")]
+    fn unseeded() -> Result<u64, HtwError> {
+        Ok(rand::random())
+    }
+
+    #[cfg(not(feature = "entropy"))]
+    fn unseeded() -> Result<u64, HtwError> {
+        Err(HtwError::SeedNotSet)
+    }
+
+    #[cfg(test)]
+    mod tests_for_game_builder {
+        use super::*;
+        use crate::connection::connection::Connection;
+        use crate::direction::direction::Direction;
+        use crate::hazards::hazards::SpiderWebs;
+        use crate::htw_game::htw_game::GameStatus;
+        use crate::htw_message::htw_message::HtwMessageLog;
+        use alloc::collections::BTreeSet;
+
+        fn set_up() -> GameBuilder {
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+            ]);
+            GameBuilder::new(caverns)
+                .message_receiver(Box::new(HtwMessageLog::new()))
+                .player_cavern("cavern")
+                .wumpus_cavern("cavern_w")
+                .seed(0)
+        }
+
+        #[test]
+        fn test_build() {
+            let game = set_up()
+                .bat_cavern("cavern_e")
+                .pit_cavern("cavern_s")
+                .quiver(5)
+                .build()
+                .unwrap();
+            assert_eq!("cavern", game.get_player_cavern());
+            assert_eq!("cavern_w", game.get_wumpus_cavern());
+            assert_eq!(5, game.get_quiver());
+            assert_eq!(10, game.get_hit_points());
+            assert_eq!(&GameStatus::Playing, game.get_status());
+        }
+
+        #[test]
+        fn test_build_with_config() {
+            let config = GameConfig {
+                max_hit_points: 3,
+                ..GameConfig::default()
+            };
+            let game = set_up().config(config).build().unwrap();
+            assert_eq!(3, game.get_hit_points());
+            assert_eq!(3, game.get_max_hit_points());
+        }
+
+        #[test]
+        fn test_build_with_seed() {
+            let play = || {
+                let connections = Connections::new(vec![
+                    Connection::new("cavern", "cavern_e", &Direction::East),
+                    Connection::new("cavern_e", "cavern", &Direction::West),
+                ]);
+                let mut game = set_up()
+                    .connections(connections)
+                    .bat_cavern("cavern_e")
+                    .seed(3)
+                    .build()
+                    .unwrap();
+                game.make_move_command(Direction::East);
+                game.execute_command();
+                String::from(game.get_player_cavern())
+            };
+            assert_eq!(play(), play());
+        }
+
+        #[test]
+        fn test_build_without_message_receiver() {
+            let result = GameBuilder::new(BTreeSet::from([String::from("cavern")])).build();
+            assert_eq!(Some(HtwError::MessageReceiverNotSet), result.err());
+        }
+
+        #[test]
+        fn test_build_without_seed() {
+            let caverns = BTreeSet::from([String::from("cavern"), String::from("cavern_n")]);
+            let result = GameBuilder::new(caverns)
+                .message_receiver(Box::new(HtwMessageLog::new()))
+                .player_cavern("cavern")
+                .wumpus_cavern("cavern_n")
+                .build();
+            if cfg!(feature = "entropy") {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(Some(HtwError::SeedNotSet), result.err());
+            }
+        }
+
+        #[test]
+        fn test_build_without_wumpus_cavern() {
+            let caverns = BTreeSet::from([String::from("cavern"), String::from("cavern_n")]);
+            let result = GameBuilder::new(caverns)
+                .message_receiver(Box::new(HtwMessageLog::new()))
+                .player_cavern("cavern")
+                .seed(0)
+                .build();
+            assert_eq!(Some(HtwError::WumpusCavernNotSet), result.err());
+        }
+
+        #[test]
+        fn test_build_with_unknown_cavern() {
+            let result = set_up().pit_cavern("cavern_x").build();
+            assert_eq!(
+                Some(HtwError::UnknownCavern(String::from("cavern_x"))),
+                result.err()
+            );
+        }
+
+        #[test]
+        fn test_build_player_starts_in_hazard() {
+            let mut webs = SpiderWebs::new();
+            webs.add_cavern("cavern");
+            let result = set_up().hazard(Box::new(webs)).build();
+            assert_eq!(
+                Some(HtwError::PlayerStartsInHazard(String::from("spider webs"))),
+                result.err()
+            );
+        }
+    }
+}
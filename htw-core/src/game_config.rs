@@ -0,0 +1,31 @@
+pub mod game_config {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct GameConfig {
+        pub max_hit_points: u32,
+        pub rest_healing: u32,
+        pub safe_bat_drop: bool,
+    }
+
+    impl Default for GameConfig {
+        fn default() -> GameConfig {
+            GameConfig {
+                max_hit_points: 10,
+                rest_healing: 1,
+                safe_bat_drop: false,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_game_config {
+        use super::*;
+
+        #[test]
+        fn test_default() {
+            let config = GameConfig::default();
+            assert_eq!(10, config.max_hit_points);
+            assert_eq!(1, config.rest_healing);
+            assert!(!config.safe_bat_drop);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+pub mod game_setup {
+    use crate::connections::connections::{self, Connections, MapGenerator};
+    use crate::difficulty::difficulty::Preset;
+    use crate::game_builder::game_builder::GameBuilder;
+    use crate::htw_error::htw_error::HtwError;
+    use crate::htw_game::htw_game::Caverns;
+    use crate::{HtwMessageReceiver, HuntTheWumpus};
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeSet;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, RngCore, SeedableRng};
+
+    // the most caverns a cave is generated with. there are far more names than this, and
+    // connecting bigger caves would take long.
+    pub const MAX_CAVERNS: usize = 1000;
+
+    const ENVIRONMENTS: [&str; 10] = [
+        "bright", "humid", "dry", "creepy", "ugly", "foggy", "hot", "cold", "drafty", "dreadful",
+    ];
+    const SHAPES: [&str; 9] = [
+        "round",
+        "square",
+        "oval",
+        "irregular",
+        "long",
+        "craggy",
+        "rough",
+        "tall",
+        "narrow",
+    ];
+    const CAVERN_TYPES: [&str; 10] = [
+        "cavern",
+        "room",
+        "chamber",
+        "catacomb",
+        "crevasse",
+        "cell",
+        "tunnel",
+        "passageway",
+        "hall",
+        "expanse",
+    ];
+    const ADORNMENTS: [&str; 12] = [
+        "smelling of sulphur",
+        "with engravings on the walls",
+        "with a bumpy floor",
+        "",
+        "littered with garbage",
+        "spattered with guano",
+        "with piles of Wumpus droppings",
+        "with bones scattered around",
+        "with a corpse on the floor",
+        "that seems to vibrate",
+        "that feels stuffy",
+        "that fills you with dread",
+    ];
+    // create_caverns would never finish if it needed more names than there are.
+    const _: () = assert!(
+        MAX_CAVERNS <= ENVIRONMENTS.len() * SHAPES.len() * CAVERN_TYPES.len() * ADORNMENTS.len()
+    );
+
+    // how to set up a random game. the same setup and rng always give the same game.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct GameSetup {
+        pub preset: Preset,
+        pub generator: MapGenerator,
+        // a number of caverns, instead of one picked from the range of the preset.
+        pub caverns: Option<usize>,
+    }
+
+    impl GameSetup {
+        pub fn new(preset: Preset) -> GameSetup {
+            GameSetup {
+                preset,
+                generator: MapGenerator::Random,
+                caverns: None,
+            }
+        }
+
+        pub fn generate_cave(
+            &self,
+            rng: &mut dyn RngCore,
+        ) -> Result<(Caverns, Connections), HtwError> {
+            let n_caverns = match self.caverns {
+                Some(n) => n,
+                None => rng.gen_range(self.preset.caverns.clone()),
+            };
+            if n_caverns > MAX_CAVERNS {
+                return Err(HtwError::TooManyCaverns {
+                    caverns: n_caverns,
+                    max: MAX_CAVERNS,
+                });
+            }
+            let caverns = create_caverns(n_caverns, rng);
+            let connections = Connections::generate(self.generator, &caverns, rng)?;
+            Ok((caverns, connections))
+        }
+
+        // puts the player, the wumpus, the bats and the pits in caverns of their own.
+        pub fn build(
+            &self,
+            (caverns, connections): (Caverns, Connections),
+            rng: &mut dyn RngCore,
+            message_receiver: Box<dyn HtwMessageReceiver>,
+        ) -> Result<Box<dyn HuntTheWumpus>, HtwError> {
+            let (n_bats, n_pits) = (self.preset.bats, self.preset.pits);
+            if 2 + n_bats + n_pits > caverns.len() {
+                return Err(HtwError::TooFewCaverns {
+                    caverns: caverns.len(),
+                    bats: n_bats,
+                    pits: n_pits,
+                });
+            }
+            let mut special_caverns: Vec<String> =
+                connections::sorted(&caverns).into_iter().cloned().collect();
+            special_caverns.shuffle(rng);
+
+            let mut builder = GameBuilder::new(caverns)
+                .connections(connections)
+                .message_receiver(message_receiver)
+                .config(self.preset.config.clone())
+                .seed(rng.gen())
+                .player_cavern(&special_caverns[0])
+                .wumpus_cavern(&special_caverns[1])
+                .quiver(self.preset.arrows);
+            for cavern in &special_caverns[2..2 + n_bats] {
+                builder = builder.bat_cavern(cavern);
+            }
+            for cavern in &special_caverns[2 + n_bats..2 + n_bats + n_pits] {
+                builder = builder.pit_cavern(cavern);
+            }
+            builder.build()
+        }
+
+        // the game that `--seed` sets up.
+        pub fn build_from_seed(
+            &self,
+            seed: u64,
+            message_receiver: Box<dyn HtwMessageReceiver>,
+        ) -> Result<Box<dyn HuntTheWumpus>, HtwError> {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let cave = self.generate_cave(&mut rng)?;
+            self.build(cave, &mut rng, message_receiver)
+        }
+    }
+
+    fn create_caverns(n_caverns: usize, rng: &mut dyn RngCore) -> Caverns {
+        let mut caverns = BTreeSet::new();
+        while caverns.len() < n_caverns {
+            caverns.insert(make_name(rng));
+        }
+        caverns
+    }
+
+    fn make_name(rng: &mut dyn RngCore) -> String {
+        String::from("A ")
+            + choose_name(&ENVIRONMENTS, rng)
+            + " "
+            + choose_name(&SHAPES, rng)
+            + " "
+            + choose_name(&CAVERN_TYPES, rng)
+            + " "
+            + choose_name(&ADORNMENTS, rng)
+    }
+
+    fn choose_name<'a>(names: &[&'a str], rng: &mut dyn RngCore) -> &'a str {
+        let n = names.len();
+        let choice = rng.gen_range(0..n);
+        names[choice]
+    }
+
+    #[cfg(test)]
+    mod tests_for_game_setup {
+        use super::*;
+        use crate::difficulty::difficulty::Difficulty;
+        use crate::htw_message::htw_message::HtwMessageLog;
+
+        fn set_up() -> GameSetup {
+            let mut setup = GameSetup::new(Difficulty::Normal.preset());
+            setup.caverns = Some(20);
+            setup
+        }
+
+        #[test]
+        fn test_generate_cave() {
+            let setup = set_up();
+            let (caverns, connections) =
+                setup.generate_cave(&mut StdRng::seed_from_u64(1)).unwrap();
+            assert_eq!(20, caverns.len());
+            assert_eq!(Ok(()), connections.validate(&caverns));
+        }
+
+        #[test]
+        fn test_build_same_game_from_same_seed() {
+            let setup = set_up();
+            let play = || {
+                let mut rng = StdRng::seed_from_u64(7);
+                let cave = setup.generate_cave(&mut rng).unwrap();
+                let log = HtwMessageLog::new();
+                let game = setup.build(cave, &mut rng, Box::new(log)).unwrap();
+                (
+                    String::from(game.get_player_cavern()),
+                    String::from(game.get_wumpus_cavern()),
+                    game.get_quiver(),
+                )
+            };
+            assert_eq!(play(), play());
+            assert_eq!(5, play().2);
+            let game = setup
+                .build_from_seed(7, Box::new(HtwMessageLog::new()))
+                .unwrap();
+            assert_eq!(play().0, game.get_player_cavern());
+        }
+
+        #[test]
+        fn test_build_with_too_few_caverns() {
+            let mut setup = set_up();
+            setup.caverns = Some(7);
+            let mut rng = StdRng::seed_from_u64(1);
+            let cave = setup.generate_cave(&mut rng).unwrap();
+            let result = setup.build(cave, &mut rng, Box::new(HtwMessageLog::new()));
+            assert_eq!(
+                Some(HtwError::TooFewCaverns {
+                    caverns: 7,
+                    bats: 3,
+                    pits: 3
+                }),
+                result.err()
+            );
+        }
+
+        #[test]
+        fn test_generate_cave_with_too_many_caverns() {
+            let mut setup = set_up();
+            setup.caverns = Some(20000);
+            let result = setup.build_from_seed(1, Box::new(HtwMessageLog::new()));
+            assert_eq!(
+                Some(HtwError::TooManyCaverns {
+                    caverns: 20000,
+                    max: MAX_CAVERNS
+                }),
+                result.err()
+            );
+        }
+    }
+}
@@ -0,0 +1,454 @@
+pub mod hazards {
+    use crate::connections::connections::sorted;
+    use crate::htw_game::htw_game::Caverns;
+    use crate::HtwMessageReceiver;
+    use alloc::collections::BTreeSet;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use rand::{Rng, RngCore};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum HazardEffect {
+        Damage(u32),
+        Teleport(String),
+        Block,
+        SkipTurn,
+    }
+
+    pub struct HazardContext<'a> {
+        pub caverns: &'a Caverns,
+        pub wumpus_cavern: &'a str,
+        // caverns holding any hazard or the wumpus.
+        pub unsafe_caverns: &'a Caverns,
+        pub rng: &'a mut dyn RngCore,
+    }
+
+    pub trait Hazard {
+        fn name(&self) -> &str;
+        fn caverns(&self) -> &Caverns;
+        fn add_cavern(&mut self, cavern: &str);
+        // called when the player is next to one of the hazard's caverns.
+        fn percept(&self, message_receiver: &dyn HtwMessageReceiver);
+        // called when the player enters one of the hazard's caverns.
+        fn enter(
+            &mut self,
+            cavern: &str,
+            context: &mut HazardContext,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> HazardEffect;
+        // whether the hazard leaves the cavern the player ran into it in, as bats do.
+        fn relocates(&self) -> bool {
+            false
+        }
+    }
+
+    pub struct Pits {
+        caverns: Caverns,
+    }
+    impl Pits {
+        pub fn new() -> Pits {
+            Pits {
+                caverns: BTreeSet::new(),
+            }
+        }
+    }
+    impl Default for Pits {
+        fn default() -> Pits {
+            Pits::new()
+        }
+    }
+    impl Hazard for Pits {
+        fn name(&self) -> &str {
+            "pits"
+        }
+
+        fn caverns(&self) -> &Caverns {
+            &self.caverns
+        }
+
+        fn add_cavern(&mut self, cavern: &str) {
+            self.caverns.insert(String::from(cavern));
+        }
+
+        fn percept(&self, message_receiver: &dyn HtwMessageReceiver) {
+            message_receiver.hear_pit();
+        }
+
+        fn enter(
+            &mut self,
+            _cavern: &str,
+            _context: &mut HazardContext,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> HazardEffect {
+            message_receiver.fell_in_pit();
+            HazardEffect::Damage(4)
+        }
+    }
+
+    pub struct Bats {
+        caverns: Caverns,
+        safe_drop: bool,
+    }
+    impl Bats {
+        pub fn new(safe_drop: bool) -> Bats {
+            Bats {
+                caverns: BTreeSet::new(),
+                safe_drop,
+            }
+        }
+
+        fn randomly_transport_player(
+            &self,
+            caverns: &Caverns,
+            player_cavern: &str,
+            unsafe_caverns: &Caverns,
+            rng: &mut dyn RngCore,
+        ) -> String {
+            let mut transport_choices: Vec<&String> = sorted(caverns)
+                .into_iter()
+                .filter(|c| *c != player_cavern)
+                .collect();
+            let safe_choices: Vec<&String> = transport_choices
+                .iter()
+                .filter(|c| !unsafe_caverns.contains(**c))
+                .copied()
+                .collect();
+            // when every cavern is dangerous, the bats drop the player anywhere.
+            if !safe_choices.is_empty() {
+                transport_choices = safe_choices;
+            }
+            let n_choices = transport_choices.len();
+            let choice = rng.gen_range(0..n_choices);
+            transport_choices[choice].to_string()
+        }
+
+        fn relocate(
+            &mut self,
+            caverns: &Caverns,
+            from: &str,
+            landing_cavern: &str,
+            rng: &mut dyn RngCore,
+        ) {
+            let bat_choices: Vec<&String> = sorted(caverns)
+                .into_iter()
+                .filter(|c| *c != landing_cavern && !self.caverns.contains(*c))
+                .collect();
+            if bat_choices.is_empty() {
+                return;
+            }
+            let choice = rng.gen_range(0..bat_choices.len());
+            let new_bat_cavern = bat_choices[choice].to_string();
+            self.caverns.remove(from);
+            self.caverns.insert(new_bat_cavern);
+        }
+    }
+    impl Hazard for Bats {
+        fn name(&self) -> &str {
+            "bats"
+        }
+
+        fn caverns(&self) -> &Caverns {
+            &self.caverns
+        }
+
+        fn add_cavern(&mut self, cavern: &str) {
+            self.caverns.insert(String::from(cavern));
+        }
+
+        fn percept(&self, message_receiver: &dyn HtwMessageReceiver) {
+            message_receiver.hear_bats();
+        }
+
+        fn enter(
+            &mut self,
+            cavern: &str,
+            context: &mut HazardContext,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> HazardEffect {
+            message_receiver.bats_transport();
+            let no_unsafe_caverns = BTreeSet::new();
+            let unsafe_caverns = match self.safe_drop {
+                true => context.unsafe_caverns,
+                false => &no_unsafe_caverns,
+            };
+            let landing_cavern = self.randomly_transport_player(
+                context.caverns,
+                cavern,
+                unsafe_caverns,
+                context.rng,
+            );
+            self.relocate(context.caverns, cavern, &landing_cavern, context.rng);
+            HazardEffect::Teleport(landing_cavern)
+        }
+
+        fn relocates(&self) -> bool {
+            true
+        }
+    }
+
+    pub struct SpiderWebs {
+        caverns: Caverns,
+    }
+    impl SpiderWebs {
+        pub fn new() -> SpiderWebs {
+            SpiderWebs {
+                caverns: BTreeSet::new(),
+            }
+        }
+    }
+    impl Default for SpiderWebs {
+        fn default() -> SpiderWebs {
+            SpiderWebs::new()
+        }
+    }
+    impl Hazard for SpiderWebs {
+        fn name(&self) -> &str {
+            "spider webs"
+        }
+
+        fn caverns(&self) -> &Caverns {
+            &self.caverns
+        }
+
+        fn add_cavern(&mut self, cavern: &str) {
+            self.caverns.insert(String::from(cavern));
+        }
+
+        fn percept(&self, message_receiver: &dyn HtwMessageReceiver) {
+            message_receiver.see_webs();
+        }
+
+        fn enter(
+            &mut self,
+            _cavern: &str,
+            _context: &mut HazardContext,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> HazardEffect {
+            message_receiver.caught_in_web();
+            HazardEffect::SkipTurn
+        }
+    }
+
+    pub struct PoisonGas {
+        caverns: Caverns,
+    }
+    impl PoisonGas {
+        pub fn new() -> PoisonGas {
+            PoisonGas {
+                caverns: BTreeSet::new(),
+            }
+        }
+    }
+    impl Default for PoisonGas {
+        fn default() -> PoisonGas {
+            PoisonGas::new()
+        }
+    }
+    impl Hazard for PoisonGas {
+        fn name(&self) -> &str {
+            "poison gas"
+        }
+
+        fn caverns(&self) -> &Caverns {
+            &self.caverns
+        }
+
+        fn add_cavern(&mut self, cavern: &str) {
+            self.caverns.insert(String::from(cavern));
+        }
+
+        fn percept(&self, message_receiver: &dyn HtwMessageReceiver) {
+            message_receiver.smell_gas();
+        }
+
+        fn enter(
+            &mut self,
+            _cavern: &str,
+            _context: &mut HazardContext,
+            message_receiver: &dyn HtwMessageReceiver,
+        ) -> HazardEffect {
+            message_receiver.breathe_gas();
+            HazardEffect::Damage(2)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_hazards {
+        use super::*;
+        use crate::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+
+        fn set_up() -> (HtwMessageLog, Caverns) {
+            let message_receiver = HtwMessageLog::new();
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            (message_receiver, caverns)
+        }
+
+        #[test]
+        fn test_add_cavern() {
+            let mut pits = Pits::new();
+            pits.add_cavern("cavern_n");
+            assert_eq!(&BTreeSet::from([String::from("cavern_n")]), pits.caverns());
+        }
+
+        #[test]
+        fn test_enter_pit() {
+            let (message_receiver, caverns) = set_up();
+            let unsafe_caverns = BTreeSet::new();
+            let mut context = HazardContext {
+                caverns: &caverns,
+                wumpus_cavern: "cavern_w",
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut rand::thread_rng(),
+            };
+            let mut pits = Pits::new();
+            pits.add_cavern("cavern_n");
+            let result = pits.enter("cavern_n", &mut context, &message_receiver);
+            assert_eq!(HazardEffect::Damage(4), result);
+            assert_eq!(Some(HtwMessage::FellInPit), message_receiver.last_message());
+        }
+
+        #[test]
+        fn test_enter_bats() {
+            let (message_receiver, caverns) = set_up();
+            let unsafe_caverns = BTreeSet::new();
+            let mut context = HazardContext {
+                caverns: &caverns,
+                wumpus_cavern: "cavern_w",
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut rand::thread_rng(),
+            };
+            let mut bats = Bats::new(false);
+            bats.add_cavern("cavern_n");
+            let result = bats.enter("cavern_n", &mut context, &message_receiver);
+            let landing_cavern = match result {
+                HazardEffect::Teleport(cavern) => cavern,
+                _ => panic!("bats should carry the player away"),
+            };
+            assert_ne!("cavern_n", landing_cavern);
+            assert_eq!(
+                Some(HtwMessage::BatsTransport),
+                message_receiver.last_message()
+            );
+            // the bats flew off to a new cavern.
+            assert_eq!(1, bats.caverns().len());
+            assert!(!bats.caverns().contains("cavern_n"));
+            assert!(!bats.caverns().contains(&landing_cavern));
+        }
+
+        #[test]
+        fn test_randomly_transport_player() {
+            let (_, caverns) = set_up();
+            let bats = Bats::new(false);
+            let result = bats.randomly_transport_player(
+                &caverns,
+                "cavern",
+                &BTreeSet::new(),
+                &mut rand::thread_rng(),
+            );
+            assert_ne!(String::from("cavern"), result);
+        }
+
+        #[test]
+        fn test_randomly_transport_player_avoids_unsafe_caverns() {
+            let (_, caverns) = set_up();
+            let bats = Bats::new(true);
+            let unsafe_caverns = BTreeSet::from([
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+            ]);
+            for _ in 0..20 {
+                let result = bats.randomly_transport_player(
+                    &caverns,
+                    "cavern",
+                    &unsafe_caverns,
+                    &mut rand::thread_rng(),
+                );
+                assert_eq!(String::from("cavern_nn"), result);
+            }
+        }
+
+        #[test]
+        fn test_randomly_transport_player_when_no_cavern_is_safe() {
+            let (_, caverns) = set_up();
+            let bats = Bats::new(true);
+            let result = bats.randomly_transport_player(
+                &caverns,
+                "cavern",
+                &caverns,
+                &mut rand::thread_rng(),
+            );
+            assert_ne!(String::from("cavern"), result);
+        }
+
+        #[test]
+        fn test_enter_bats_with_safe_drop() {
+            let (message_receiver, caverns) = set_up();
+            let unsafe_caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_s"),
+            ]);
+            let mut context = HazardContext {
+                caverns: &caverns,
+                wumpus_cavern: "cavern_w",
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut rand::thread_rng(),
+            };
+            let mut bats = Bats::new(true);
+            bats.add_cavern("cavern_n");
+            let result = bats.enter("cavern_n", &mut context, &message_receiver);
+            assert_eq!(HazardEffect::Teleport(String::from("cavern_nn")), result);
+        }
+
+        #[test]
+        fn test_relocate_bats_nowhere_to_go() {
+            let caverns = BTreeSet::from([String::from("cavern"), String::from("cavern_n")]);
+            let mut bats = Bats::new(false);
+            bats.add_cavern("cavern_n");
+            bats.relocate(&caverns, "cavern_n", "cavern", &mut rand::thread_rng());
+            assert_eq!(&BTreeSet::from([String::from("cavern_n")]), bats.caverns());
+        }
+
+        #[test]
+        fn test_enter_spider_webs() {
+            let (message_receiver, caverns) = set_up();
+            let unsafe_caverns = BTreeSet::new();
+            let mut context = HazardContext {
+                caverns: &caverns,
+                wumpus_cavern: "cavern_w",
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut rand::thread_rng(),
+            };
+            let mut webs = SpiderWebs::new();
+            webs.add_cavern("cavern_n");
+            let result = webs.enter("cavern_n", &mut context, &message_receiver);
+            assert_eq!(HazardEffect::SkipTurn, result);
+        }
+
+        #[test]
+        fn test_enter_poison_gas() {
+            let (message_receiver, caverns) = set_up();
+            let unsafe_caverns = BTreeSet::new();
+            let mut context = HazardContext {
+                caverns: &caverns,
+                wumpus_cavern: "cavern_w",
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut rand::thread_rng(),
+            };
+            let mut gas = PoisonGas::new();
+            gas.add_cavern("cavern_n");
+            let result = gas.enter("cavern_n", &mut context, &message_receiver);
+            assert_eq!(HazardEffect::Damage(2), result);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+pub mod htw_error {
+    use crate::direction::direction::Direction;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use core::error::Error;
+    use core::fmt;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum HtwError {
+        NoCaverns,
+        MessageReceiverNotSet,
+        // without the entropy feature, every game needs a seed.
+        SeedNotSet,
+        NoOtherCavern(String),
+        UnknownCavern(String),
+        PlayerCavernNotSet,
+        WumpusCavernNotSet,
+        PlayerStartsWithWumpus,
+        PlayerStartsInHazard(String),
+        InvalidMapLine(usize, String),
+        InvalidMap(Vec<MapViolation>),
+        TooFewCaverns {
+            caverns: usize,
+            bats: usize,
+            pits: usize,
+        },
+        TooManyCaverns {
+            caverns: usize,
+            max: usize,
+        },
+    }
+
+    // a way the passages of a cave can be broken.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum MapViolation {
+        MissingReverse {
+            from: String,
+            to: String,
+            direction: Direction,
+        },
+        WrongReverseDirection {
+            from: String,
+            to: String,
+            direction: Direction,
+            reverse: Direction,
+        },
+        DuplicateDirection {
+            cavern: String,
+            direction: Direction,
+        },
+        DoubleLink {
+            from: String,
+            to: String,
+        },
+        SelfLoop {
+            cavern: String,
+            direction: Direction,
+        },
+        UnknownCavern(String),
+        Unreachable(String),
+    }
+
+    impl fmt::Display for HtwError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                HtwError::NoCaverns => write!(f, "there are no caverns"),
+                HtwError::MessageReceiverNotSet => write!(f, "the message receiver is not set"),
+                HtwError::SeedNotSet => write!(f, "the seed is not set"),
+                HtwError::NoOtherCavern(cavern) => {
+                    write!(f, "there is no cavern other than \"{}\"", cavern)
+                }
+                HtwError::UnknownCavern(cavern) => write!(f, "unknown cavern \"{}\"", cavern),
+                HtwError::PlayerCavernNotSet => write!(f, "the player cavern is not set"),
+                HtwError::WumpusCavernNotSet => write!(f, "the wumpus cavern is not set"),
+                HtwError::PlayerStartsWithWumpus => {
+                    write!(f, "the player starts in the wumpus cavern")
+                }
+                HtwError::PlayerStartsInHazard(name) => {
+                    write!(f, "the player starts in a cavern with {}", name)
+                }
+                HtwError::InvalidMapLine(line, reason) => {
+                    write!(f, "map line {}: {}", line, reason)
+                }
+                HtwError::InvalidMap(violations) => {
+                    let violations: Vec<String> =
+                        violations.iter().map(|v| v.to_string()).collect();
+                    write!(f, "invalid map: {}", violations.join("; "))
+                }
+                HtwError::TooFewCaverns {
+                    caverns,
+                    bats,
+                    pits,
+                } => write!(
+                    f,
+                    "{} caverns cannot hold the player, the wumpus, {} bats and {} pits",
+                    caverns, bats, pits
+                ),
+                HtwError::TooManyCaverns { caverns, max } => {
+                    write!(
+                        f,
+                        "{} caverns are more than the {} a cave can have",
+                        caverns, max
+                    )
+                }
+            }
+        }
+    }
+
+    impl fmt::Display for MapViolation {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let name = |direction: &Direction| direction.name().to_lowercase();
+            match self {
+                MapViolation::MissingReverse {
+                    from,
+                    to,
+                    direction,
+                } => write!(
+                    f,
+                    "the passage {} from \"{}\" to \"{}\" has no way back",
+                    name(direction),
+                    from,
+                    to
+                ),
+                MapViolation::WrongReverseDirection {
+                    from,
+                    to,
+                    direction,
+                    reverse,
+                } => write!(
+                    f,
+                    "the passage {} from \"{}\" to \"{}\" leads back {}, not {}",
+                    name(direction),
+                    from,
+                    to,
+                    name(reverse),
+                    name(&direction.opposite())
+                ),
+                MapViolation::DuplicateDirection { cavern, direction } => write!(
+                    f,
+                    "\"{}\" has more than one passage {}",
+                    cavern,
+                    name(direction)
+                ),
+                MapViolation::DoubleLink { from, to } => {
+                    write!(f, "\"{}\" has more than one passage to \"{}\"", from, to)
+                }
+                MapViolation::SelfLoop { cavern, direction } => write!(
+                    f,
+                    "the passage {} from \"{}\" leads back to itself",
+                    name(direction),
+                    cavern
+                ),
+                MapViolation::UnknownCavern(cavern) => {
+                    write!(f, "a passage leads to unknown cavern \"{}\"", cavern)
+                }
+                MapViolation::Unreachable(cavern) => {
+                    write!(f, "\"{}\" cannot be reached", cavern)
+                }
+            }
+        }
+    }
+
+    impl Error for HtwError {}
+
+    #[cfg(test)]
+    mod tests_for_htw_error {
+        use super::*;
+        use alloc::vec;
+
+        #[test]
+        fn test_display() {
+            assert_eq!(
+                "unknown cavern \"cavern_x\"",
+                HtwError::UnknownCavern(String::from("cavern_x")).to_string()
+            );
+            assert_eq!(
+                "the player starts in a cavern with pits",
+                HtwError::PlayerStartsInHazard(String::from("pits")).to_string()
+            );
+            assert_eq!(
+                "invalid map: the passage north from \"a\" to \"b\" leads back east, not south; \
+                 \"c\" cannot be reached",
+                HtwError::InvalidMap(vec![
+                    MapViolation::WrongReverseDirection {
+                        from: String::from("a"),
+                        to: String::from("b"),
+                        direction: Direction::North,
+                        reverse: Direction::East,
+                    },
+                    MapViolation::Unreachable(String::from("c")),
+                ])
+                .to_string()
+            );
+        }
+    }
+}
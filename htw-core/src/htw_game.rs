@@ -0,0 +1,1464 @@
+pub mod htw_game {
+    use crate::commands::commands::{MoveCommand, RestCommand, ShootCommand};
+    use crate::connections::connections::{Connections, MapGenerator};
+    use crate::game_config::game_config::GameConfig;
+    use crate::hazards::hazards::{Bats, Hazard, HazardContext, HazardEffect, Pits};
+    use crate::htw_error::htw_error::HtwError;
+    use crate::inference::inference::{Assessment, Inference};
+    use crate::knowledge_map::knowledge_map::{KnowledgeMap, WUMPUS};
+    use crate::map_export::map_export::{MapFormat, MapMarks};
+    use crate::Direction;
+    use crate::HtwMessageReceiver;
+    use crate::HuntTheWumpus;
+    use alloc::boxed::Box;
+    use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::fmt;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    pub type Caverns = BTreeSet<String>;
+    pub type ArrowsIn = BTreeMap<String, u32>;
+
+    // the hazards may keep resolving each other, e.g. bats dropping the player among other bats.
+    const MAX_HAZARD_RESOLUTIONS: u32 = 100;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum DeathCause {
+        Hazard(String),
+        WalkedIntoWumpus,
+        FoundByWumpus,
+        ShotSelf,
+        ShotWall,
+    }
+
+    impl DeathCause {
+        // a one-word name for front ends that are programs, e.g. "shot-wall", or the hazard's
+        // name with dashes for spaces, e.g. "spider-webs".
+        pub fn name(&self) -> String {
+            match self {
+                DeathCause::Hazard(name) => name.replace(' ', "-"),
+                DeathCause::WalkedIntoWumpus => String::from("walked-into-wumpus"),
+                DeathCause::FoundByWumpus => String::from("wumpus-found-you"),
+                DeathCause::ShotSelf => String::from("shot-self"),
+                DeathCause::ShotWall => String::from("shot-wall"),
+            }
+        }
+    }
+
+    impl fmt::Display for DeathCause {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                DeathCause::Hazard(name) => write!(f, "{}", name),
+                DeathCause::WalkedIntoWumpus => write!(f, "walked into the wumpus"),
+                DeathCause::FoundByWumpus => write!(f, "found by the wumpus"),
+                DeathCause::ShotSelf => write!(f, "shot self"),
+                DeathCause::ShotWall => write!(f, "shot the wall"),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum GameStatus {
+        Playing,
+        Won,
+        Lost(DeathCause),
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    pub struct CommandResult {
+        pub new_player_cavern: Option<String>,
+        pub new_quiver: Option<u32>,
+        pub update_arrows_in: Option<ArrowsIn>,
+        pub self_damage: Option<(u32, DeathCause)>,
+        pub healing: Option<u32>,
+        pub killed_wumpus: bool,
+    }
+
+    /// The phases of a turn, in the order `execute_command` runs them.
+    ///
+    /// 1. `PlayerAction`: the command runs. Shooting, resting and arrow damage apply here.
+    ///    A player stuck in a web loses this phase.
+    /// 2. `HazardResolution`: only when the player changed caverns. The wumpus and the hazards
+    ///    of the cavern are checked, and again wherever the player is carried off to, until the
+    ///    player stays put. Arrows lying in the final cavern are picked up last.
+    /// 3. `WumpusTurn`: the wumpus moves and may find the player.
+    /// 4. `Percepts`: passages and nearby dangers are reported.
+    ///
+    /// Once the game is won or lost, the remaining phases and their hooks are skipped.
+    /// Hooks added with `GameBuilder::turn_hook` run right after the phase they are attached to.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum TurnPhase {
+        PlayerAction,
+        HazardResolution,
+        WumpusTurn,
+        Percepts,
+    }
+
+    impl TurnPhase {
+        pub const ORDER: [TurnPhase; 4] = [
+            TurnPhase::PlayerAction,
+            TurnPhase::HazardResolution,
+            TurnPhase::WumpusTurn,
+            TurnPhase::Percepts,
+        ];
+    }
+
+    pub trait TurnHook {
+        fn run(&mut self, turn: &mut Turn);
+    }
+
+    // what a turn hook can see and do in the middle of a turn.
+    pub struct Turn<'a> {
+        game: &'a mut HuntTheWumpusGame,
+    }
+
+    impl Turn<'_> {
+        pub fn message_receiver(&self) -> &dyn HtwMessageReceiver {
+            self.game.message_receiver.as_ref()
+        }
+
+        pub fn connections(&self) -> &Connections {
+            &self.game.connections
+        }
+
+        pub fn caverns(&self) -> &Caverns {
+            &self.game.caverns
+        }
+
+        pub fn player_cavern(&self) -> &str {
+            &self.game.player_cavern
+        }
+
+        pub fn wumpus_cavern(&self) -> &str {
+            &self.game.wumpus_cavern
+        }
+
+        pub fn hit_points(&self) -> u32 {
+            self.game.hit_points
+        }
+
+        pub fn quiver(&self) -> u32 {
+            self.game.quiver
+        }
+
+        pub fn status(&self) -> &GameStatus {
+            &self.game.status
+        }
+
+        pub fn hit(&mut self, points: u32, cause: DeathCause) {
+            self.game.hit(points, cause);
+        }
+
+        pub fn move_player(&mut self, cavern: &str) {
+            self.game.enter_cavern(cavern);
+        }
+
+        pub fn move_wumpus(&mut self, cavern: &str) {
+            self.game.wumpus_cavern = String::from(cavern);
+            self.game.check_wumpus_moved_to_player();
+        }
+    }
+
+    pub trait Command {
+        fn process_command(
+            &self,
+            message_receiver: &dyn HtwMessageReceiver,
+            connections: &Connections,
+            player_cavern: &str,
+            wumpus_cavern: &str,
+            quiver: u32,
+            arrows_in: &ArrowsIn,
+        ) -> CommandResult;
+    }
+
+    pub struct HuntTheWumpusGame {
+        connections: Connections,
+        caverns: Caverns,
+        player_cavern: String,
+        message_receiver: Box<dyn HtwMessageReceiver>,
+        hazards: Vec<Box<dyn Hazard>>,
+        wumpus_cavern: String,
+        quiver: u32,
+        arrows_in: ArrowsIn,
+        command: Box<dyn Command>,
+        hit_points: u32,
+        skip_turns: u32,
+        moved_from: Option<String>,
+        // set when a hazard or a wound breaks into the turn.
+        interrupted: bool,
+        status: GameStatus,
+        knowledge: KnowledgeMap,
+        turn_hooks: Vec<(TurnPhase, Box<dyn TurnHook>)>,
+        config: GameConfig,
+        rng: StdRng,
+    }
+
+    impl HuntTheWumpusGame {
+        // games are put together and validated by the GameBuilder.
+        pub(crate) fn new(
+            message_receiver: Box<dyn HtwMessageReceiver>,
+            caverns: Caverns,
+            config: GameConfig,
+        ) -> HuntTheWumpusGame {
+            HuntTheWumpusGame {
+                connections: Connections::new(vec![]),
+                caverns,
+                player_cavern: String::new(),
+                message_receiver,
+                hazards: vec![
+                    Box::new(Pits::new()),
+                    Box::new(Bats::new(config.safe_bat_drop)),
+                ],
+                wumpus_cavern: String::new(),
+                quiver: 0,
+                arrows_in: BTreeMap::new(),
+                command: Box::new(RestCommand::new(config.rest_healing)),
+                hit_points: config.max_hit_points,
+                skip_turns: 0,
+                moved_from: None,
+                interrupted: false,
+                status: GameStatus::Playing,
+                knowledge: KnowledgeMap::new(),
+                turn_hooks: vec![],
+                config,
+                // the GameBuilder always seeds it.
+                rng: StdRng::seed_from_u64(0),
+            }
+        }
+
+        fn move_wumpus(&mut self) {
+            let mut wumpus_choices = vec![];
+            for c in &self.connections.connections {
+                if self.wumpus_cavern == c.from() {
+                    wumpus_choices.push(c.to());
+                }
+            }
+            wumpus_choices.push(&self.wumpus_cavern);
+
+            let n_choices = wumpus_choices.len();
+            let choice = self.rng.gen_range(0..n_choices);
+            self.wumpus_cavern = String::from(wumpus_choices[choice]);
+        }
+
+        fn is_over(&self) -> bool {
+            self.status != GameStatus::Playing
+        }
+
+        fn hit(&mut self, points: u32, cause: DeathCause) {
+            self.hit_points = self.hit_points.saturating_sub(points);
+            self.interrupted = true;
+            if self.hit_points == 0 && !self.is_over() {
+                self.message_receiver.you_die();
+                self.status = GameStatus::Lost(cause);
+            }
+        }
+
+        fn hazard_mut(&mut self, name: &str) -> Option<&mut Box<dyn Hazard>> {
+            self.hazards.iter_mut().find(|h| h.name() == name)
+        }
+
+        fn unsafe_caverns(&self) -> Caverns {
+            let mut unsafe_caverns = BTreeSet::from([String::from(&self.wumpus_cavern)]);
+            for hazard in &self.hazards {
+                unsafe_caverns.extend(hazard.caverns().iter().cloned());
+            }
+            unsafe_caverns
+        }
+
+        fn check_for_wumpus(&mut self) {
+            if self.player_cavern == self.wumpus_cavern {
+                self.message_receiver.player_moves_to_wumpus();
+                self.status = GameStatus::Lost(DeathCause::WalkedIntoWumpus);
+            }
+        }
+
+        // applies the effects of every hazard in the player's cavern.
+        // returns the cavern the player was carried off to, if any.
+        fn resolve_hazards(&mut self, previous_cavern: &str) -> Option<String> {
+            let unsafe_caverns = self.unsafe_caverns();
+            let mut context = HazardContext {
+                caverns: &self.caverns,
+                wumpus_cavern: &self.wumpus_cavern,
+                unsafe_caverns: &unsafe_caverns,
+                rng: &mut self.rng,
+            };
+            let mut effects = vec![];
+            for hazard in self.hazards.iter_mut() {
+                if hazard.caverns().contains(&self.player_cavern) {
+                    self.interrupted = true;
+                    let effect = hazard.enter(
+                        &self.player_cavern,
+                        &mut context,
+                        self.message_receiver.as_ref(),
+                    );
+                    effects.push((String::from(hazard.name()), effect));
+                }
+            }
+
+            let cavern = self.player_cavern.clone();
+            let mut teleport = None;
+            for (name, effect) in effects {
+                self.knowledge.found(&cavern, &name);
+                match effect {
+                    HazardEffect::Damage(points) => self.hit(points, DeathCause::Hazard(name)),
+                    HazardEffect::Teleport(cavern) => teleport = Some(cavern),
+                    HazardEffect::Block => self.player_cavern = String::from(previous_cavern),
+                    HazardEffect::SkipTurn => self.skip_turns += 1,
+                }
+            }
+            teleport
+        }
+
+        fn check_for_arrows(&mut self) {
+            let arrows_found = self.arrows_in.remove(&self.player_cavern).unwrap_or(0);
+            if arrows_found > 0 {
+                self.message_receiver.arrows_found(arrows_found);
+                self.quiver += arrows_found;
+            }
+        }
+
+        fn enter_cavern(&mut self, cavern: &str) {
+            let previous_cavern = core::mem::replace(&mut self.player_cavern, String::from(cavern));
+            self.resolve_arrival(previous_cavern);
+        }
+
+        // checks the cavern the player arrived in, and any cavern the hazards carry them to,
+        // until the player stays put or the game ends.
+        fn resolve_arrival(&mut self, mut previous_cavern: String) {
+            for _ in 0..MAX_HAZARD_RESOLUTIONS {
+                self.knowledge.visit(&self.player_cavern);
+                self.check_for_wumpus();
+                if self.is_over() {
+                    return;
+                }
+                match self.resolve_hazards(&previous_cavern) {
+                    Some(landing_cavern) if !self.is_over() => {
+                        previous_cavern =
+                            core::mem::replace(&mut self.player_cavern, landing_cavern);
+                    }
+                    _ => break,
+                }
+            }
+            if !self.is_over() {
+                self.check_for_arrows();
+            }
+        }
+
+        fn player_action(&mut self) {
+            if self.skip_turns > 0 {
+                self.skip_turns -= 1;
+                self.interrupted = true;
+                self.message_receiver.player_stuck();
+                return;
+            }
+            let result = self.command.process_command(
+                self.message_receiver.as_ref(),
+                &self.connections,
+                &self.player_cavern,
+                &self.wumpus_cavern,
+                self.quiver,
+                &self.arrows_in,
+            );
+            if let Some(s) = result.new_quiver {
+                self.quiver = s;
+            }
+            if let Some(s) = result.update_arrows_in {
+                self.arrows_in.extend(s);
+            }
+            if result.killed_wumpus {
+                self.status = GameStatus::Won;
+            }
+            if let Some((points, cause)) = result.self_damage {
+                self.hit(points, cause);
+            }
+            if let Some(u) = result.healing {
+                self.heal(u);
+            }
+            if let Some(s) = result.new_player_cavern {
+                self.record_passage(&s);
+                self.moved_from = Some(core::mem::replace(&mut self.player_cavern, s));
+            }
+        }
+
+        // the player walked through to `cavern`, wherever the hazards there carry them.
+        fn record_passage(&mut self, cavern: &str) {
+            let passage = self
+                .connections
+                .connections
+                .iter()
+                .find(|c| c.from() == self.player_cavern && c.to() == cavern);
+            if let Some(passage) = passage {
+                let direction = *passage.direction();
+                self.knowledge.link(&self.player_cavern, direction, cavern);
+            }
+        }
+
+        // records what report_status tells the player about their cavern.
+        pub(crate) fn observe(&mut self) {
+            let passages = self
+                .connections
+                .report_available_directions(&self.player_cavern);
+            let mut percepts: Vec<String> = self
+                .hazards
+                .iter()
+                .filter(|h| {
+                    self.connections
+                        .report_nearby(&self.player_cavern, h.caverns())
+                })
+                .map(|h| String::from(h.name()))
+                .collect();
+            if self.connections.report_nearby(
+                &self.player_cavern,
+                &BTreeSet::from([String::from(&self.wumpus_cavern)]),
+            ) {
+                percepts.push(String::from(WUMPUS));
+            }
+            self.knowledge
+                .observe(&self.player_cavern, passages, percepts);
+        }
+
+        fn hazard_resolution(&mut self) {
+            if let Some(previous_cavern) = self.moved_from.take() {
+                self.resolve_arrival(previous_cavern);
+            }
+        }
+
+        fn wumpus_turn(&mut self) {
+            self.move_wumpus();
+            self.check_wumpus_moved_to_player();
+        }
+
+        fn run_phase(&mut self, phase: TurnPhase) {
+            match phase {
+                TurnPhase::PlayerAction => self.player_action(),
+                TurnPhase::HazardResolution => self.hazard_resolution(),
+                TurnPhase::WumpusTurn => self.wumpus_turn(),
+                TurnPhase::Percepts => {
+                    self.observe();
+                    self.report_status();
+                }
+            }
+        }
+
+        fn run_turn_hooks(&mut self, phase: TurnPhase) {
+            let mut turn_hooks = core::mem::take(&mut self.turn_hooks);
+            for (hook_phase, hook) in turn_hooks.iter_mut() {
+                if *hook_phase == phase && !self.is_over() {
+                    hook.run(&mut Turn { game: self });
+                }
+            }
+            turn_hooks.append(&mut self.turn_hooks);
+            self.turn_hooks = turn_hooks;
+        }
+
+        fn heal(&mut self, points: u32) {
+            let recovered = points.min(self.config.max_hit_points.saturating_sub(self.hit_points));
+            self.hit_points += recovered;
+            self.message_receiver.player_rests(recovered);
+        }
+
+        fn check_cavern(&self, cavern: &str) -> Result<(), HtwError> {
+            if self.caverns.contains(cavern) {
+                Ok(())
+            } else {
+                Err(HtwError::UnknownCavern(String::from(cavern)))
+            }
+        }
+
+        pub(crate) fn set_player_cavern(&mut self, player_cavern: &str) -> Result<(), HtwError> {
+            self.check_cavern(player_cavern)?;
+            self.player_cavern = String::from(player_cavern);
+            Ok(())
+        }
+
+        pub(crate) fn add_bat_cavern(&mut self, cavern: &str) -> Result<(), HtwError> {
+            self.check_cavern(cavern)?;
+            if self.hazard_mut("bats").is_none() {
+                self.add_hazard(Box::new(Bats::new(self.config.safe_bat_drop)))?;
+            }
+            if let Some(bats) = self.hazard_mut("bats") {
+                bats.add_cavern(cavern);
+            }
+            Ok(())
+        }
+
+        pub(crate) fn add_pit_cavern(&mut self, cavern: &str) -> Result<(), HtwError> {
+            self.check_cavern(cavern)?;
+            if self.hazard_mut("pits").is_none() {
+                self.add_hazard(Box::new(Pits::new()))?;
+            }
+            if let Some(pits) = self.hazard_mut("pits") {
+                pits.add_cavern(cavern);
+            }
+            Ok(())
+        }
+
+        pub(crate) fn add_hazard(&mut self, hazard: Box<dyn Hazard>) -> Result<(), HtwError> {
+            for cavern in hazard.caverns() {
+                self.check_cavern(cavern)?;
+            }
+            self.hazards.push(hazard);
+            Ok(())
+        }
+
+        pub(crate) fn set_wumpus_cavern(&mut self, wumpus_cavern: &str) -> Result<(), HtwError> {
+            self.check_cavern(wumpus_cavern)?;
+            self.wumpus_cavern = String::from(wumpus_cavern);
+            Ok(())
+        }
+
+        pub(crate) fn set_quiver(&mut self, arrows: u32) {
+            self.quiver = arrows;
+        }
+
+        pub(crate) fn add_turn_hook(&mut self, phase: TurnPhase, hook: Box<dyn TurnHook>) {
+            self.turn_hooks.push((phase, hook));
+        }
+
+        fn check_wumpus_moved_to_player(&mut self) {
+            if self.player_cavern == self.wumpus_cavern && !self.is_over() {
+                self.message_receiver.wumpus_moves_to_player();
+                self.status = GameStatus::Lost(DeathCause::FoundByWumpus);
+            }
+        }
+
+        pub(crate) fn set_seed(&mut self, seed: u64) {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+
+        pub(crate) fn set_connections(&mut self, connections: Connections) {
+            self.connections = connections;
+        }
+
+        pub(crate) fn connect_caverns(&mut self) -> Result<(), HtwError> {
+            self.connections =
+                Connections::generate(MapGenerator::Random, &self.caverns, &mut self.rng)?;
+            Ok(())
+        }
+
+        pub(crate) fn validate(&self) -> Result<(), HtwError> {
+            if self.caverns.is_empty() {
+                return Err(HtwError::NoCaverns);
+            }
+            if self.player_cavern.is_empty() {
+                return Err(HtwError::PlayerCavernNotSet);
+            }
+            if self.wumpus_cavern.is_empty() {
+                return Err(HtwError::WumpusCavernNotSet);
+            }
+            self.check_cavern(&self.player_cavern)?;
+            self.check_cavern(&self.wumpus_cavern)?;
+            for hazard in &self.hazards {
+                for cavern in hazard.caverns() {
+                    self.check_cavern(cavern)?;
+                }
+            }
+            for connection in &self.connections.connections {
+                self.check_cavern(connection.from())?;
+                self.check_cavern(connection.to())?;
+            }
+            if self.player_cavern == self.wumpus_cavern {
+                return Err(HtwError::PlayerStartsWithWumpus);
+            }
+            for hazard in &self.hazards {
+                if hazard.caverns().contains(&self.player_cavern) {
+                    return Err(HtwError::PlayerStartsInHazard(String::from(hazard.name())));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl HuntTheWumpus for HuntTheWumpusGame {
+        fn get_player_cavern(&self) -> &str {
+            &self.player_cavern
+        }
+        fn get_wumpus_cavern(&self) -> &str {
+            &self.wumpus_cavern
+        }
+        fn get_quiver(&self) -> u32 {
+            self.quiver
+        }
+        fn get_hit_points(&self) -> u32 {
+            self.hit_points
+        }
+        fn get_max_hit_points(&self) -> u32 {
+            self.config.max_hit_points
+        }
+        fn get_status(&self) -> &GameStatus {
+            &self.status
+        }
+        fn was_interrupted(&self) -> bool {
+            self.interrupted || self.is_over()
+        }
+        fn report_status(&self) {
+            let directions = self
+                .connections
+                .report_available_directions(&self.player_cavern);
+            for direction in directions {
+                self.message_receiver.passage(&direction);
+            }
+
+            for hazard in &self.hazards {
+                if self
+                    .connections
+                    .report_nearby(&self.player_cavern, hazard.caverns())
+                {
+                    hazard.percept(self.message_receiver.as_ref());
+                }
+            }
+            if self.connections.report_nearby(
+                &self.player_cavern,
+                &BTreeSet::from([String::from(&self.wumpus_cavern)]),
+            ) {
+                self.message_receiver.smell_wumpus();
+            }
+        }
+        fn execute_command(&mut self) {
+            self.interrupted = false;
+            for phase in TurnPhase::ORDER {
+                if self.is_over() {
+                    return;
+                }
+                self.run_phase(phase);
+                self.run_turn_hooks(phase);
+            }
+        }
+        fn make_rest_command(&mut self) {
+            self.command = Box::new(RestCommand::new(self.config.rest_healing));
+        }
+        fn make_shoot_command(&mut self, direction: Direction) {
+            self.command = Box::new(ShootCommand::new(direction));
+        }
+        fn make_move_command(&mut self, direction: Direction) {
+            self.command = Box::new(MoveCommand::new(direction));
+        }
+        fn caverns(&self) -> &Caverns {
+            &self.caverns
+        }
+        fn knowledge_map(&self) -> &KnowledgeMap {
+            &self.knowledge
+        }
+        // the chance of each hazard in a cavern nothing is known about is its share of the
+        // caverns.
+        fn assess_caverns(&self) -> Vec<Assessment> {
+            let mut inference = Inference::new(&self.knowledge, &self.player_cavern);
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for hazard in &self.hazards {
+                *counts.entry(hazard.name()).or_insert(0) += hazard.caverns().len();
+                if hazard.relocates() {
+                    inference = inference.relocating(hazard.name());
+                }
+            }
+            for (name, count) in counts {
+                inference = inference.prior(name, count as f64 / self.caverns.len() as f64);
+            }
+            inference.assess()
+        }
+        // the shortest way through caverns the player has looked around, along passages they
+        // have walked, avoiding caverns where they ran into a hazard, the target among them.
+        fn route_to(&self, cavern: &str) -> Option<Vec<(Direction, String)>> {
+            let mut previous: BTreeMap<&str, (Direction, &str)> = BTreeMap::new();
+            let mut queue = VecDeque::from([self.player_cavern.as_str()]);
+            while let Some(current) = queue.pop_front() {
+                if current == cavern {
+                    let mut route = vec![];
+                    let mut step = current;
+                    while let Some((direction, from)) = previous.get(step) {
+                        route.push((*direction, String::from(step)));
+                        step = from;
+                    }
+                    route.reverse();
+                    return Some(route);
+                }
+                let Some(knowledge) = self.knowledge.cavern(current) else {
+                    continue;
+                };
+                for direction in &knowledge.passages {
+                    let Some(next) = self.knowledge.destination(current, *direction) else {
+                        continue;
+                    };
+                    let safe = match self.knowledge.cavern(next) {
+                        Some(knowledge) => knowledge.observed && knowledge.found.is_empty(),
+                        None => false,
+                    };
+                    if safe && next != self.player_cavern && !previous.contains_key(next) {
+                        previous.insert(next, (*direction, current));
+                        queue.push_back(next);
+                    }
+                }
+            }
+            None
+        }
+        fn export_map(&self, format: MapFormat) -> String {
+            let mut marks = MapMarks {
+                player: Some(self.player_cavern.clone()),
+                wumpus: Some(self.wumpus_cavern.clone()),
+                hazards: BTreeMap::new(),
+                arrows: self.arrows_in.clone(),
+            };
+            for hazard in &self.hazards {
+                for cavern in hazard.caverns() {
+                    marks
+                        .hazards
+                        .entry(cavern.clone())
+                        .or_default()
+                        .push(String::from(hazard.name()));
+                }
+            }
+            self.connections.export(format, &self.caverns, &marks)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_hunt_the_wumpus_game {
+        use super::*;
+        use crate::connection::connection::Connection;
+        use crate::hazards::hazards::SpiderWebs;
+        use crate::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+        use crate::RecordingMessageReceiver;
+
+        fn type_of<T>(_: &T) -> &str {
+            core::any::type_name::<T>()
+        }
+
+        struct Boulders {
+            caverns: Caverns,
+        }
+        impl Hazard for Boulders {
+            fn name(&self) -> &str {
+                "boulders"
+            }
+            fn caverns(&self) -> &Caverns {
+                &self.caverns
+            }
+            fn add_cavern(&mut self, cavern: &str) {
+                self.caverns.insert(String::from(cavern));
+            }
+            fn percept(&self, _message_receiver: &dyn HtwMessageReceiver) {}
+            fn enter(
+                &mut self,
+                _cavern: &str,
+                _context: &mut HazardContext,
+                _message_receiver: &dyn HtwMessageReceiver,
+            ) -> HazardEffect {
+                HazardEffect::Block
+            }
+        }
+
+        struct Teleporter {
+            caverns: Caverns,
+            destination: String,
+        }
+        impl Hazard for Teleporter {
+            fn name(&self) -> &str {
+                "teleporter"
+            }
+            fn caverns(&self) -> &Caverns {
+                &self.caverns
+            }
+            fn add_cavern(&mut self, cavern: &str) {
+                self.caverns.insert(String::from(cavern));
+            }
+            fn percept(&self, _message_receiver: &dyn HtwMessageReceiver) {}
+            fn enter(
+                &mut self,
+                _cavern: &str,
+                _context: &mut HazardContext,
+                _message_receiver: &dyn HtwMessageReceiver,
+            ) -> HazardEffect {
+                HazardEffect::Teleport(String::from(&self.destination))
+            }
+        }
+
+        struct Earthquake {}
+        impl TurnHook for Earthquake {
+            fn run(&mut self, turn: &mut Turn) {
+                turn.hit(10, DeathCause::Hazard(String::from("earthquake")));
+            }
+        }
+
+        fn hazard_caverns(game: &HuntTheWumpusGame, name: &str) -> Caverns {
+            let hazard = game.hazards.iter().find(|h| h.name() == name).unwrap();
+            hazard.caverns().clone()
+        }
+
+        fn set_up() -> HuntTheWumpusGame {
+            let message_receiver = Box::new(HtwMessageLog::new());
+            let caverns = BTreeSet::from([
+                String::from("cavern"),
+                String::from("cavern_w"),
+                String::from("cavern_e"),
+                String::from("cavern_n"),
+                String::from("cavern_s"),
+                String::from("cavern_nn"),
+            ]);
+            let connections = vec![
+                Connection::new("cavern", "cavern_w", &Direction::West),
+                Connection::new("cavern_w", "cavern", &Direction::East),
+                Connection::new("cavern", "cavern_e", &Direction::East),
+                Connection::new("cavern_e", "cavern", &Direction::West),
+                Connection::new("cavern", "cavern_n", &Direction::North),
+                Connection::new("cavern_n", "cavern", &Direction::South),
+                Connection::new("cavern", "cavern_s", &Direction::South),
+                Connection::new("cavern_s", "cavern", &Direction::North),
+                Connection::new("cavern_n", "cavern_nn", &Direction::North),
+                Connection::new("cavern_nn", "cavern_n", &Direction::South),
+            ];
+            let connections = Connections::new(connections);
+            let player_cavern = String::from("cavern");
+            let mut pits = Pits::new();
+            pits.add_cavern("cavern_s");
+            let mut bats = Bats::new(false);
+            bats.add_cavern("cavern_e");
+            let hazards: Vec<Box<dyn Hazard>> = vec![Box::new(pits), Box::new(bats)];
+            let wumpus_cavern = String::from("cavern_w");
+            let quiver = 5;
+            let arrows_in = BTreeMap::new();
+            let command = Box::new(RestCommand::new(1));
+            let hit_points = 10;
+            let skip_turns = 0;
+            let moved_from = None;
+            let interrupted = false;
+            let status = GameStatus::Playing;
+            let knowledge = KnowledgeMap::new();
+            let turn_hooks = vec![];
+            let config = GameConfig::default();
+            let rng = StdRng::seed_from_u64(0);
+            HuntTheWumpusGame {
+                message_receiver,
+                caverns,
+                connections,
+                player_cavern,
+                hazards,
+                wumpus_cavern,
+                quiver,
+                arrows_in,
+                command,
+                hit_points,
+                skip_turns,
+                moved_from,
+                interrupted,
+                status,
+                knowledge,
+                turn_hooks,
+                config,
+                rng,
+            }
+        }
+
+        // the game from set_up, with the messages it sends recorded.
+        fn set_up_recorded() -> (HuntTheWumpusGame, RecordingMessageReceiver) {
+            let log = RecordingMessageReceiver::new();
+            let mut game = set_up();
+            game.message_receiver = Box::new(log.clone());
+            (game, log)
+        }
+
+        #[test]
+        fn test_move_wumpus() {
+            let mut game = set_up();
+            let mut count = 0;
+            while &game.wumpus_cavern == "cavern_w" || count == 100 {
+                game.move_wumpus();
+                count += 1;
+            }
+            assert_ne!(&game.wumpus_cavern, "cavern_w");
+        }
+
+        #[test]
+        fn test_hit() {
+            let mut game = set_up();
+            assert_eq!(10, game.hit_points);
+            game.hit(3, DeathCause::ShotWall);
+            assert_eq!(7, game.hit_points);
+            assert_eq!(GameStatus::Playing, game.status);
+            game.hit(8, DeathCause::ShotSelf);
+            assert_eq!(0, game.hit_points);
+            assert_eq!(GameStatus::Lost(DeathCause::ShotSelf), game.status);
+        }
+
+        #[test]
+        fn test_heal() {
+            let mut game = set_up();
+            game.hit(3, DeathCause::ShotWall);
+            game.heal(1);
+            assert_eq!(8, game.hit_points);
+        }
+
+        #[test]
+        fn test_heal_up_to_max_hit_points() {
+            let mut game = set_up();
+            game.hit(1, DeathCause::ShotWall);
+            game.heal(5);
+            assert_eq!(game.config.max_hit_points, game.hit_points);
+        }
+
+        #[test]
+        fn test_unsafe_caverns() {
+            let game = set_up();
+            assert_eq!(
+                BTreeSet::from([
+                    String::from("cavern_w"),
+                    String::from("cavern_s"),
+                    String::from("cavern_e"),
+                ]),
+                game.unsafe_caverns()
+            );
+        }
+
+        #[test]
+        fn test_resolve_hazards_no_hazard() {
+            let mut game = set_up();
+            game.player_cavern = String::from("cavern_n");
+            assert_eq!(None, game.resolve_hazards("cavern"));
+            assert_eq!(10, game.hit_points);
+        }
+
+        #[test]
+        fn test_resolve_hazards_pit() {
+            let mut game = set_up();
+            game.player_cavern = String::from("cavern_s");
+            assert_eq!(None, game.resolve_hazards("cavern"));
+            assert_eq!(6, game.hit_points);
+            let knowledge = game.knowledge_map().cavern("cavern_s").unwrap();
+            assert_eq!(vec![String::from("pits")], knowledge.found);
+        }
+
+        #[test]
+        fn test_resolve_hazards_bats() {
+            let mut game = set_up();
+            game.player_cavern = String::from("cavern_e");
+            let landing_cavern = game.resolve_hazards("cavern").unwrap();
+            assert_ne!("cavern_e", landing_cavern);
+            assert!(!hazard_caverns(&game, "bats").contains("cavern_e"));
+        }
+
+        #[test]
+        fn test_resolve_hazards_spider_webs() {
+            let mut game = set_up();
+            game.add_hazard(Box::new(SpiderWebs::new())).unwrap();
+            game.hazard_mut("spider webs")
+                .unwrap()
+                .add_cavern("cavern_n");
+            game.player_cavern = String::from("cavern_n");
+            assert_eq!(None, game.resolve_hazards("cavern"));
+            assert_eq!(1, game.skip_turns);
+        }
+
+        #[test]
+        fn test_resolve_hazards_block() {
+            let mut game = set_up();
+            game.add_hazard(Box::new(Boulders {
+                caverns: BTreeSet::from([String::from("cavern_n")]),
+            }))
+            .unwrap();
+            game.player_cavern = String::from("cavern_n");
+            assert_eq!(None, game.resolve_hazards("cavern"));
+            assert_eq!("cavern", game.player_cavern);
+        }
+
+        #[test]
+        fn test_enter_cavern_picks_up_arrows() {
+            let (mut game, log) = set_up_recorded();
+            game.arrows_in.insert(String::from("cavern_n"), 2);
+            game.enter_cavern("cavern_n");
+            assert_eq!("cavern_n", game.player_cavern);
+            assert_eq!(vec![HtwMessage::ArrowsFound(2)], log.messages());
+            assert_eq!(7, game.quiver);
+            assert_eq!(None, game.arrows_in.get("cavern_n"));
+        }
+
+        #[test]
+        fn test_enter_cavern_carried_by_bats() {
+            let mut game = set_up();
+            // keep the wumpus out of the way so the bats cannot drop the player on it.
+            game.wumpus_cavern = String::from("none");
+            game.enter_cavern("cavern_e");
+            assert_ne!("cavern_e", game.player_cavern);
+        }
+
+        #[test]
+        fn test_resolve_arrival_checks_wumpus_at_landing_cavern() {
+            let mut game = set_up();
+            game.add_hazard(Box::new(Teleporter {
+                caverns: BTreeSet::from([String::from("cavern_n")]),
+                destination: String::from("cavern_w"),
+            }))
+            .unwrap();
+            game.player_cavern = String::from("cavern_n");
+            game.resolve_arrival(String::from("cavern"));
+            assert_eq!("cavern_w", game.player_cavern);
+            assert_eq!(GameStatus::Lost(DeathCause::WalkedIntoWumpus), game.status);
+        }
+
+        #[test]
+        fn test_resolve_arrival_stops_looping() {
+            let mut game = set_up();
+            game.add_hazard(Box::new(Teleporter {
+                caverns: BTreeSet::from([String::from("cavern_n")]),
+                destination: String::from("cavern_n"),
+            }))
+            .unwrap();
+            game.player_cavern = String::from("cavern_n");
+            game.resolve_arrival(String::from("cavern"));
+            assert_eq!("cavern_n", game.player_cavern);
+            assert_eq!(GameStatus::Playing, game.status);
+        }
+
+        #[test]
+        fn test_execute_command_moves_player() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            assert_eq!("cavern_n", game.player_cavern);
+            assert_eq!(GameStatus::Playing, game.status);
+            assert!(!game.was_interrupted());
+        }
+
+        #[test]
+        fn test_observe() {
+            let mut game = set_up();
+            game.observe();
+            let knowledge = game.knowledge_map().cavern("cavern").unwrap();
+            assert_eq!(4, knowledge.passages.len());
+            assert_eq!(
+                vec![
+                    String::from("pits"),
+                    String::from("bats"),
+                    String::from(WUMPUS)
+                ],
+                knowledge.percepts
+            );
+        }
+
+        #[test]
+        fn test_execute_command_records_knowledge() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            let knowledge_map = game.knowledge_map();
+            assert_eq!(
+                Some("cavern_n"),
+                knowledge_map.destination("cavern", Direction::North)
+            );
+            assert_eq!(
+                Some("cavern"),
+                knowledge_map.destination("cavern_n", Direction::South)
+            );
+            let knowledge = knowledge_map.cavern("cavern_n").unwrap();
+            assert_eq!(vec![Direction::South, Direction::North], knowledge.passages);
+            assert!(knowledge.percepts.is_empty());
+        }
+
+        #[test]
+        fn test_execute_command_interrupted_by_pit() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.make_move_command(Direction::South);
+            game.execute_command();
+            assert!(game.was_interrupted());
+
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            assert!(!game.was_interrupted());
+        }
+
+        #[test]
+        fn test_execute_command_stuck_in_web() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.skip_turns = 1;
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            assert_eq!("cavern", game.player_cavern);
+            assert_eq!(0, game.skip_turns);
+            assert!(game.was_interrupted());
+        }
+
+        #[test]
+        fn test_execute_command_wumpus_stays_after_player_dies() {
+            let mut game = set_up();
+            game.hit_points = 1;
+            game.make_move_command(Direction::South);
+            game.execute_command();
+            assert_eq!(
+                GameStatus::Lost(DeathCause::Hazard(String::from("pits"))),
+                game.status
+            );
+            assert_eq!("cavern_w", game.wumpus_cavern);
+        }
+
+        #[test]
+        fn test_execute_command_kills_wumpus() {
+            let mut game = set_up();
+            game.make_shoot_command(Direction::West);
+            game.execute_command();
+            assert_eq!(GameStatus::Won, game.status);
+            assert_eq!("cavern_w", game.wumpus_cavern);
+        }
+
+        #[test]
+        fn test_execute_command_does_nothing_when_game_is_over() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.status = GameStatus::Won;
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            assert_eq!("cavern", game.player_cavern);
+        }
+
+        #[test]
+        fn test_execute_command_runs_turn_hooks() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.add_turn_hook(TurnPhase::PlayerAction, Box::new(Earthquake {}));
+            game.make_move_command(Direction::North);
+            game.execute_command();
+            assert_eq!(
+                GameStatus::Lost(DeathCause::Hazard(String::from("earthquake"))),
+                game.status
+            );
+            // the player died before the hazards were resolved.
+            assert_eq!(Some(String::from("cavern")), game.moved_from);
+        }
+
+        #[test]
+        fn test_check_for_arrows_no_arrows() {
+            let (mut game, log) = set_up_recorded();
+            game.check_for_arrows();
+            assert_eq!(5, game.quiver);
+            assert!(log.messages().is_empty());
+        }
+
+        #[test]
+        fn test_set_player_cavern() {
+            let mut game = set_up();
+            assert_ne!(&game.player_cavern, "cavern_n");
+            game.set_player_cavern("cavern_n").unwrap();
+            assert_eq!(&game.player_cavern, "cavern_n");
+        }
+
+        #[test]
+        fn test_get_player_cavern() {
+            let game = set_up();
+            assert_eq!(game.get_player_cavern(), "cavern");
+        }
+
+        #[test]
+        fn test_add_bat_cavern() {
+            let mut game = set_up();
+            assert_eq!(
+                hazard_caverns(&game, "bats"),
+                BTreeSet::from([String::from("cavern_e")])
+            );
+            game.add_bat_cavern("cavern_nn").unwrap();
+            assert_eq!(
+                hazard_caverns(&game, "bats"),
+                BTreeSet::from([String::from("cavern_e"), String::from("cavern_nn")])
+            );
+        }
+
+        #[test]
+        fn test_add_pit_cavern() {
+            let mut game = set_up();
+            assert_eq!(
+                hazard_caverns(&game, "pits"),
+                BTreeSet::from([String::from("cavern_s")])
+            );
+            game.add_pit_cavern("cavern_nn").unwrap();
+            assert_eq!(
+                hazard_caverns(&game, "pits"),
+                BTreeSet::from([String::from("cavern_s"), String::from("cavern_nn")])
+            );
+        }
+
+        #[test]
+        fn test_set_wumpus_cavern() {
+            let mut game = set_up();
+            assert_eq!(&game.wumpus_cavern, "cavern_w");
+            game.set_wumpus_cavern("cavern_nn").unwrap();
+            assert_eq!(&game.wumpus_cavern, "cavern_nn");
+        }
+
+        #[test]
+        fn test_set_unknown_caverns() {
+            let mut game = set_up();
+            let unknown_cavern = Err(HtwError::UnknownCavern(String::from("cavern_x")));
+            assert_eq!(unknown_cavern, game.set_player_cavern("cavern_x"));
+            assert_eq!(unknown_cavern, game.set_wumpus_cavern("cavern_x"));
+            assert_eq!(unknown_cavern, game.add_bat_cavern("cavern_x"));
+            assert_eq!(unknown_cavern, game.add_pit_cavern("cavern_x"));
+            let mut webs = SpiderWebs::new();
+            webs.add_cavern("cavern_x");
+            assert_eq!(unknown_cavern, game.add_hazard(Box::new(webs)));
+            assert_eq!("cavern", game.player_cavern);
+            assert_eq!("cavern_w", game.wumpus_cavern);
+            assert_eq!(2, game.hazards.len());
+        }
+
+        #[test]
+        fn test_validate() {
+            let game = set_up();
+            assert_eq!(Ok(()), game.validate());
+        }
+
+        #[test]
+        fn test_validate_caverns_not_set() {
+            let game = HuntTheWumpusGame::new(
+                Box::new(HtwMessageLog::new()),
+                BTreeSet::from([String::from("cavern")]),
+                GameConfig::default(),
+            );
+            assert_eq!(Err(HtwError::PlayerCavernNotSet), game.validate());
+
+            let game = HuntTheWumpusGame::new(
+                Box::new(HtwMessageLog::new()),
+                BTreeSet::new(),
+                GameConfig::default(),
+            );
+            assert_eq!(Err(HtwError::NoCaverns), game.validate());
+        }
+
+        #[test]
+        fn test_validate_player_starts_in_danger() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("cavern");
+            assert_eq!(Err(HtwError::PlayerStartsWithWumpus), game.validate());
+
+            let mut game = set_up();
+            game.player_cavern = String::from("cavern_s");
+            assert_eq!(
+                Err(HtwError::PlayerStartsInHazard(String::from("pits"))),
+                game.validate()
+            );
+        }
+
+        #[test]
+        fn test_validate_unknown_connection() {
+            let mut game = set_up();
+            game.connections.connections.push(Connection::new(
+                "cavern",
+                "cavern_x",
+                &Direction::West,
+            ));
+            assert_eq!(
+                Err(HtwError::UnknownCavern(String::from("cavern_x"))),
+                game.validate()
+            );
+        }
+
+        #[test]
+        fn test_get_wumpus_cavern() {
+            let game = set_up();
+            assert_eq!(game.get_wumpus_cavern(), &game.wumpus_cavern);
+        }
+
+        #[test]
+        fn test_set_quiver() {
+            let mut game = set_up();
+            assert_eq!(game.quiver, 5);
+            game.set_quiver(10);
+            assert_eq!(game.quiver, 10);
+        }
+
+        #[test]
+        fn test_get_quiver() {
+            let game = set_up();
+            assert_eq!(game.get_quiver(), game.quiver);
+        }
+
+        #[test]
+        fn test_get_hit_points() {
+            let game = set_up();
+            assert_eq!(game.get_hit_points(), game.hit_points);
+        }
+
+        #[test]
+        fn test_caverns_method() {
+            let game = set_up();
+            let caverns = game.caverns();
+            assert_eq!(
+                type_of(caverns),
+                "alloc::collections::btree::set::BTreeSet<alloc::string::String>"
+            );
+            assert_eq!(caverns, &game.caverns);
+        }
+
+        #[test]
+        fn test_connect_caverns() {
+            let mut game = set_up();
+            game.connect_caverns().unwrap();
+            assert_ne!(0, game.connections.connections.len());
+        }
+
+        #[test]
+        fn test_assess_caverns() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.knowledge.found("cavern", "bats");
+            game.observe();
+            let assessments = game.assess_caverns();
+            // the bats flew off, so they may be back in 1 of the 6 caverns like anywhere else.
+            let here = &assessments[0];
+            assert_eq!(Some(String::from("cavern")), here.cavern);
+            assert_eq!(1, here.threats.len());
+            assert_eq!("bats", here.threats[0].name);
+            assert!((here.threats[0].probability - 1.0 / 6.0).abs() < 1e-9);
+            // the 1 pit in 6 caverns lies behind at least one of the 4 passages.
+            let north = assessments
+                .iter()
+                .find(|a| a.via == Some((String::from("cavern"), Direction::North)))
+                .unwrap();
+            let pits = north.threats.iter().find(|t| t.name == "pits").unwrap();
+            let prior: f64 = 1.0 / 6.0;
+            let expected = prior / (1.0 - (1.0 - prior) * (1.0 - prior).powi(3));
+            assert!((pits.probability - expected).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_route_to() {
+            let mut game = set_up();
+            game.wumpus_cavern = String::from("none");
+            game.observe();
+            for direction in [Direction::North, Direction::North] {
+                game.make_move_command(direction);
+                game.execute_command();
+            }
+            assert_eq!("cavern_nn", game.player_cavern);
+            assert_eq!(
+                Some(vec![
+                    (Direction::South, String::from("cavern_n")),
+                    (Direction::South, String::from("cavern")),
+                ]),
+                game.route_to("cavern")
+            );
+            assert_eq!(Some(vec![]), game.route_to("cavern_nn"));
+            // never visited.
+            assert_eq!(None, game.route_to("cavern_w"));
+        }
+
+        #[test]
+        fn test_route_to_avoids_hazards() {
+            let mut game = set_up();
+            for cavern in ["cavern", "cavern_w", "cavern_n", "cavern_nn"] {
+                let passages = game.connections.report_available_directions(cavern);
+                game.knowledge.observe(cavern, passages, vec![]);
+            }
+            game.knowledge.link("cavern", Direction::West, "cavern_w");
+            game.knowledge.link("cavern", Direction::North, "cavern_n");
+            game.knowledge
+                .link("cavern_n", Direction::North, "cavern_nn");
+            game.knowledge.found("cavern", "pits");
+            game.player_cavern = String::from("cavern_nn");
+            assert_eq!(None, game.route_to("cavern_w"));
+        }
+
+        #[test]
+        fn test_route_to_walked_passages_only() {
+            let mut game = set_up();
+            for cavern in ["cavern", "cavern_w"] {
+                let passages = game.connections.report_available_directions(cavern);
+                game.knowledge.observe(cavern, passages, vec![]);
+            }
+            // the passage west was seen, but where it leads is not known until it is walked.
+            assert_eq!(None, game.route_to("cavern_w"));
+            game.knowledge.link("cavern", Direction::West, "cavern_w");
+            assert_eq!(
+                Some(vec![(Direction::West, String::from("cavern_w"))]),
+                game.route_to("cavern_w")
+            );
+        }
+
+        #[test]
+        fn test_route_to_hazard() {
+            let mut game = set_up();
+            for cavern in ["cavern", "cavern_n", "cavern_nn"] {
+                let passages = game.connections.report_available_directions(cavern);
+                game.knowledge.observe(cavern, passages, vec![]);
+            }
+            game.knowledge.link("cavern", Direction::North, "cavern_n");
+            game.knowledge
+                .link("cavern_n", Direction::North, "cavern_nn");
+            game.player_cavern = String::from("cavern_nn");
+            assert_eq!(2, game.route_to("cavern").unwrap().len());
+            game.knowledge.found("cavern", "pits");
+            assert_eq!(None, game.route_to("cavern"));
+            assert_eq!(1, game.route_to("cavern_n").unwrap().len());
+        }
+
+        #[test]
+        fn test_export_map() {
+            let game = set_up();
+            let dot = game.export_map(MapFormat::Dot);
+            assert!(dot.contains("\"cavern_s\" [label=\"cavern_s\\npits\", fillcolor=\"gray\"];"));
+            assert!(
+                dot.contains("\"cavern_w\" [label=\"cavern_w\\nwumpus\", fillcolor=\"tomato\"];")
+            );
+            assert!(dot.contains("\"cavern\" -> \"cavern_n\" [label=\"N\"];"));
+        }
+
+        #[test]
+        fn test_report_status() {
+            let (game, log) = set_up_recorded();
+            game.report_status();
+            assert_eq!(
+                vec![
+                    HtwMessage::Passage(Direction::West),
+                    HtwMessage::Passage(Direction::East),
+                    HtwMessage::Passage(Direction::North),
+                    HtwMessage::Passage(Direction::South),
+                    HtwMessage::HearPit,
+                    HtwMessage::HearBats,
+                    HtwMessage::SmellWumpus,
+                ],
+                log.messages()
+            );
+        }
+
+        #[test]
+        fn test_report_status_nothing_nearby() {
+            let (mut game, log) = set_up_recorded();
+            game.player_cavern = String::from("cavern_nn");
+            game.report_status();
+            assert_eq!(vec![HtwMessage::Passage(Direction::South)], log.messages());
+        }
+
+        #[test]
+        fn test_execute_command_no_passage() {
+            let (mut game, log) = set_up_recorded();
+            game.player_cavern = String::from("cavern_nn");
+            game.make_move_command(Direction::East);
+            game.execute_command();
+            assert_eq!("cavern_nn", game.get_player_cavern());
+            assert_eq!(Some(&HtwMessage::NoPassage), log.messages().first());
+        }
+
+        #[test]
+        fn test_execute_command_shoots_wumpus() {
+            let (mut game, log) = set_up_recorded();
+            game.make_shoot_command(Direction::West);
+            game.execute_command();
+            assert_eq!(&GameStatus::Won, game.get_status());
+            assert!(log.received(&HtwMessage::ArrowShot));
+            assert_eq!(Some(HtwMessage::PlayerKillsWumpus), log.last_message());
+            // the game is over, so the wumpus does not move and nothing is reported.
+            assert!(log.passages().is_empty());
+        }
+
+        #[test]
+        fn test_execute_command_rests() {
+            let (mut game, log) = set_up_recorded();
+            game.hit_points = 5;
+            game.make_rest_command();
+            game.execute_command();
+            assert_eq!(6, game.get_hit_points());
+            assert_eq!(Some(&HtwMessage::PlayerRests(1)), log.messages().first());
+        }
+    }
+}
@@ -0,0 +1,306 @@
+pub mod htw_message {
+    use crate::direction::direction::Direction;
+    use crate::HtwMessageReceiver;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+
+    // one message per HtwMessageReceiver call, for receivers that keep or translate them.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum HtwMessage {
+        NoPassage,
+        HearBats,
+        HearPit,
+        SmellWumpus,
+        Passage(Direction),
+        NoArrows,
+        ArrowShot,
+        PlayerShootsSelfInBack,
+        PlayerKillsWumpus,
+        PlayerShootsWall,
+        ArrowsFound(u32),
+        FellInPit,
+        PlayerMovesToWumpus,
+        WumpusMovesToPlayer,
+        BatsTransport,
+        SeeWebs,
+        CaughtInWeb,
+        PlayerStuck,
+        SmellGas,
+        BreatheGas,
+        PlayerRests(u32),
+        YouDie,
+    }
+
+    impl HtwMessage {
+        // a short name for front ends that are programs, e.g. "fell-in-pit". the number or the
+        // direction a message carries is not part of its name.
+        pub fn name(&self) -> &'static str {
+            match self {
+                HtwMessage::NoPassage => "no-passage",
+                HtwMessage::HearBats => "bats",
+                HtwMessage::HearPit => "pit",
+                HtwMessage::SmellWumpus => "smell",
+                HtwMessage::Passage(_) => "passage",
+                HtwMessage::NoArrows => "no-arrows",
+                HtwMessage::ArrowShot => "arrow-shot",
+                HtwMessage::PlayerShootsSelfInBack => "shot-self",
+                HtwMessage::PlayerKillsWumpus => "killed-wumpus",
+                HtwMessage::PlayerShootsWall => "shot-wall",
+                HtwMessage::ArrowsFound(_) => "arrows-found",
+                HtwMessage::FellInPit => "fell-in-pit",
+                HtwMessage::PlayerMovesToWumpus => "walked-into-wumpus",
+                HtwMessage::WumpusMovesToPlayer => "wumpus-found-you",
+                HtwMessage::BatsTransport => "carried-by-bats",
+                HtwMessage::SeeWebs => "webs",
+                HtwMessage::CaughtInWeb => "caught-in-web",
+                HtwMessage::PlayerStuck => "stuck",
+                HtwMessage::SmellGas => "gas",
+                HtwMessage::BreatheGas => "breathed-gas",
+                HtwMessage::PlayerRests(_) => "rested",
+                HtwMessage::YouDie => "died",
+            }
+        }
+
+        // whether the message tells what the player senses nearby, rather than what happened.
+        pub fn is_percept(&self) -> bool {
+            matches!(
+                self,
+                HtwMessage::HearBats
+                    | HtwMessage::HearPit
+                    | HtwMessage::SmellWumpus
+                    | HtwMessage::SeeWebs
+                    | HtwMessage::SmellGas
+            )
+        }
+    }
+
+    // takes the messages one at a time, as HtwMessages. every sink is a HtwMessageReceiver, so
+    // the receiver calls are turned into messages in one place.
+    pub trait HtwMessageSink {
+        fn say(&self, message: HtwMessage);
+    }
+
+    // keeps every message, for front ends that show them in their own way and for tests.
+    // clones share the same messages, so one can go to the game and the other be asked.
+    #[derive(Clone, Default)]
+    pub struct HtwMessageLog {
+        messages: Rc<RefCell<Vec<HtwMessage>>>,
+    }
+
+    // what a program that embeds the game, or a test, gives it to record what the player is
+    // told: keep a clone, and ask it with messages(), last_message(), passages(), heard_bats()
+    // and the like once the game has played.
+    pub type RecordingMessageReceiver = HtwMessageLog;
+
+    impl HtwMessageLog {
+        pub fn new() -> HtwMessageLog {
+            HtwMessageLog::default()
+        }
+
+        // returns the messages received since the last call.
+        pub fn take_messages(&self) -> Vec<HtwMessage> {
+            self.messages.take()
+        }
+
+        // the messages not taken yet, in order. unlike take_messages, it keeps them.
+        pub fn messages(&self) -> Vec<HtwMessage> {
+            self.messages.borrow().clone()
+        }
+
+        pub fn last_message(&self) -> Option<HtwMessage> {
+            self.messages.borrow().last().cloned()
+        }
+
+        pub fn received(&self, message: &HtwMessage) -> bool {
+            self.messages.borrow().contains(message)
+        }
+
+        pub fn count(&self, message: &HtwMessage) -> usize {
+            self.messages
+                .borrow()
+                .iter()
+                .filter(|m| *m == message)
+                .count()
+        }
+
+        // the passages reported, in the order they were.
+        pub fn passages(&self) -> Vec<Direction> {
+            self.messages
+                .borrow()
+                .iter()
+                .filter_map(|m| match m {
+                    HtwMessage::Passage(direction) => Some(*direction),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        pub fn heard_bats(&self) -> bool {
+            self.received(&HtwMessage::HearBats)
+        }
+
+        pub fn heard_pit(&self) -> bool {
+            self.received(&HtwMessage::HearPit)
+        }
+
+        pub fn smelled_wumpus(&self) -> bool {
+            self.received(&HtwMessage::SmellWumpus)
+        }
+
+        pub fn saw_webs(&self) -> bool {
+            self.received(&HtwMessage::SeeWebs)
+        }
+
+        pub fn smelled_gas(&self) -> bool {
+            self.received(&HtwMessage::SmellGas)
+        }
+
+        pub fn died(&self) -> bool {
+            self.received(&HtwMessage::YouDie)
+        }
+    }
+
+    impl HtwMessageSink for HtwMessageLog {
+        fn say(&self, message: HtwMessage) {
+            self.messages.borrow_mut().push(message);
+        }
+    }
+
+    impl<T: HtwMessageSink> HtwMessageReceiver for T {
+        fn no_passage(&self) {
+            self.say(HtwMessage::NoPassage);
+        }
+
+        fn hear_bats(&self) {
+            self.say(HtwMessage::HearBats);
+        }
+
+        fn hear_pit(&self) {
+            self.say(HtwMessage::HearPit);
+        }
+
+        fn smell_wumpus(&self) {
+            self.say(HtwMessage::SmellWumpus);
+        }
+
+        fn passage(&self, direction: &Direction) {
+            self.say(HtwMessage::Passage(*direction));
+        }
+
+        fn no_arrows(&self) {
+            self.say(HtwMessage::NoArrows);
+        }
+
+        fn arrow_shot(&self) {
+            self.say(HtwMessage::ArrowShot);
+        }
+
+        fn player_shoots_self_in_back(&self) {
+            self.say(HtwMessage::PlayerShootsSelfInBack);
+        }
+
+        fn player_kills_wumpus(&self) {
+            self.say(HtwMessage::PlayerKillsWumpus);
+        }
+
+        fn player_shoots_wall(&self) {
+            self.say(HtwMessage::PlayerShootsWall);
+        }
+
+        fn arrows_found(&self, arrows_found: u32) {
+            self.say(HtwMessage::ArrowsFound(arrows_found));
+        }
+
+        fn fell_in_pit(&self) {
+            self.say(HtwMessage::FellInPit);
+        }
+
+        fn player_moves_to_wumpus(&self) {
+            self.say(HtwMessage::PlayerMovesToWumpus);
+        }
+
+        fn wumpus_moves_to_player(&self) {
+            self.say(HtwMessage::WumpusMovesToPlayer);
+        }
+
+        fn bats_transport(&self) {
+            self.say(HtwMessage::BatsTransport);
+        }
+
+        fn see_webs(&self) {
+            self.say(HtwMessage::SeeWebs);
+        }
+
+        fn caught_in_web(&self) {
+            self.say(HtwMessage::CaughtInWeb);
+        }
+
+        fn player_stuck(&self) {
+            self.say(HtwMessage::PlayerStuck);
+        }
+
+        fn smell_gas(&self) {
+            self.say(HtwMessage::SmellGas);
+        }
+
+        fn breathe_gas(&self) {
+            self.say(HtwMessage::BreatheGas);
+        }
+
+        fn player_rests(&self, hit_points_recovered: u32) {
+            self.say(HtwMessage::PlayerRests(hit_points_recovered));
+        }
+
+        fn you_die(&self) {
+            self.say(HtwMessage::YouDie);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_htw_message {
+        use super::*;
+        use alloc::boxed::Box;
+        use alloc::vec;
+
+        #[test]
+        fn test_message_log() {
+            let log = HtwMessageLog::new();
+            let receiver: Box<dyn HtwMessageReceiver> = Box::new(log.clone());
+            receiver.hear_bats();
+            receiver.passage(&Direction::East);
+            assert_eq!(
+                vec![HtwMessage::HearBats, HtwMessage::Passage(Direction::East)],
+                log.take_messages()
+            );
+            assert!(log.take_messages().is_empty());
+        }
+
+        #[test]
+        fn test_queries_keep_messages() {
+            let log = HtwMessageLog::new();
+            assert_eq!(None, log.last_message());
+            log.hear_bats();
+            log.passage(&Direction::North);
+            log.passage(&Direction::West);
+            log.arrows_found(2);
+            assert!(log.heard_bats());
+            assert!(!log.heard_pit());
+            assert!(!log.smelled_wumpus());
+            assert_eq!(1, log.count(&HtwMessage::HearBats));
+            assert_eq!(vec![Direction::North, Direction::West], log.passages());
+            assert_eq!(Some(HtwMessage::ArrowsFound(2)), log.last_message());
+            assert_eq!(4, log.messages().len());
+            assert_eq!(4, log.take_messages().len());
+            assert!(log.messages().is_empty());
+        }
+
+        #[test]
+        fn test_name() {
+            assert_eq!("fell-in-pit", HtwMessage::FellInPit.name());
+            assert_eq!("arrows-found", HtwMessage::ArrowsFound(2).name());
+            assert!(HtwMessage::SmellGas.is_percept());
+            assert!(!HtwMessage::Passage(Direction::East).is_percept());
+        }
+    }
+}
@@ -0,0 +1,533 @@
+pub mod inference {
+    use crate::direction::direction::Direction;
+    use crate::knowledge_map::knowledge_map::{KnowledgeMap, WUMPUS};
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // the chance of a hazard in a cavern nothing is known about.
+    pub const DEFAULT_PRIOR: f64 = 0.1;
+    // every way of placing a hazard among this many caverns is weighed; beyond it, the prior is
+    // the best guess.
+    const MAX_UNKNOWN_CAVERNS: usize = 16;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Danger {
+        Possible,
+        Certain,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Threat {
+        // the name of a hazard, or "wumpus".
+        pub name: String,
+        pub danger: Danger,
+        pub probability: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Assessment {
+        // none for a passage the player has not walked, since where it leads is not known.
+        pub cavern: Option<String>,
+        // for a cavern not visited yet, the passage the player has seen leading there.
+        pub via: Option<(String, Direction)>,
+        // what may be in the cavern, most likely first. a safe cavern has none.
+        pub threats: Vec<Threat>,
+    }
+
+    impl Assessment {
+        pub fn is_safe(&self) -> bool {
+            self.threats.is_empty()
+        }
+    }
+
+    // a cavern, or the unknown one behind a passage the player has seen but not walked. two such
+    // passages may lead to the same cavern, but nothing the player knows tells them so.
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    enum Place {
+        Cavern(String),
+        Passage(String, Direction),
+    }
+
+    // works out what may lie in the caverns the player knows of, from the percepts they had in
+    // the caverns they looked around. it goes only by what the player found out, never by the
+    // true connections. the wumpus wanders, so only the caverns next to the player are judged
+    // for it.
+    pub struct Inference<'a> {
+        knowledge_map: &'a KnowledgeMap,
+        player_cavern: &'a str,
+        priors: BTreeMap<String, f64>,
+        relocating: BTreeSet<String>,
+    }
+
+    impl<'a> Inference<'a> {
+        pub fn new(knowledge_map: &'a KnowledgeMap, player_cavern: &'a str) -> Inference<'a> {
+            Inference {
+                knowledge_map,
+                player_cavern,
+                priors: BTreeMap::new(),
+                relocating: BTreeSet::new(),
+            }
+        }
+
+        // the chance of `hazard` in a cavern nothing is known about, e.g. 0.15 for 3 pits in
+        // 20 caverns.
+        pub fn prior(mut self, hazard: &str, probability: f64) -> Inference<'a> {
+            self.priors
+                .insert(String::from(hazard), probability.clamp(0.0, 1.0));
+            self
+        }
+
+        // `hazard` leaves a cavern once the player runs into it, so finding it there says
+        // nothing about where it is now.
+        pub fn relocating(mut self, hazard: &str) -> Inference<'a> {
+            self.relocating.insert(String::from(hazard));
+            self
+        }
+
+        // the visited caverns first, then the ones their passages lead to.
+        pub fn assess(&self) -> Vec<Assessment> {
+            let places = self.known_places();
+            let mut probabilities: Vec<(String, BTreeMap<Place, f64>)> = self
+                .hazards()
+                .into_iter()
+                .map(|hazard| {
+                    let p = self.hazard_probabilities(&hazard, &places);
+                    (hazard, p)
+                })
+                .collect();
+            probabilities.push((String::from(WUMPUS), self.wumpus_probabilities()));
+
+            places
+                .into_iter()
+                .map(|(place, via)| {
+                    let mut threats: Vec<Threat> = probabilities
+                        .iter()
+                        .filter_map(|(name, p)| {
+                            let probability = *p.get(&place)?;
+                            (probability > 0.0).then(|| Threat {
+                                name: name.clone(),
+                                danger: match probability >= 1.0 - 1e-9 {
+                                    true => Danger::Certain,
+                                    false => Danger::Possible,
+                                },
+                                probability,
+                            })
+                        })
+                        .collect();
+                    threats.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+                    let cavern = match place {
+                        Place::Cavern(cavern) => Some(cavern),
+                        Place::Passage(_, _) => None,
+                    };
+                    Assessment {
+                        cavern,
+                        via,
+                        threats,
+                    }
+                })
+                .collect()
+        }
+
+        fn neighbour(&self, cavern: &str, direction: Direction) -> Place {
+            match self.knowledge_map.destination(cavern, direction) {
+                Some(next) => Place::Cavern(String::from(next)),
+                None => Place::Passage(String::from(cavern), direction),
+            }
+        }
+
+        // the places the passages the player saw in `cavern` lead to.
+        fn neighbours(&self, cavern: &str) -> Vec<Place> {
+            let mut neighbours = vec![];
+            let Some(knowledge) = self.knowledge_map.cavern(cavern) else {
+                return neighbours;
+            };
+            for direction in &knowledge.passages {
+                let neighbour = self.neighbour(cavern, *direction);
+                if !neighbours.contains(&neighbour) {
+                    neighbours.push(neighbour);
+                }
+            }
+            neighbours
+        }
+
+        fn is_visited(&self, place: &Place) -> bool {
+            match place {
+                Place::Cavern(cavern) => self.knowledge_map.cavern(cavern).is_some(),
+                Place::Passage(_, _) => false,
+            }
+        }
+
+        // the visited caverns the player looked around, with what they sensed there.
+        fn observed(&self) -> Vec<(&str, &[String])> {
+            self.knowledge_map
+                .visited()
+                .iter()
+                .filter_map(|cavern| {
+                    let knowledge = self.knowledge_map.cavern(cavern)?;
+                    knowledge
+                        .observed
+                        .then_some((cavern.as_str(), knowledge.percepts.as_slice()))
+                })
+                .collect()
+        }
+
+        fn known_places(&self) -> Vec<(Place, Option<(String, Direction)>)> {
+            let mut places: Vec<(Place, Option<(String, Direction)>)> = self
+                .knowledge_map
+                .visited()
+                .iter()
+                .map(|cavern| (Place::Cavern(cavern.clone()), None))
+                .collect();
+            let mut known: BTreeSet<Place> = places.iter().map(|(p, _)| p.clone()).collect();
+            for (cavern, _) in self.observed() {
+                let passages = &self.knowledge_map.cavern(cavern).unwrap().passages;
+                for direction in passages {
+                    let next = self.neighbour(cavern, *direction);
+                    if known.insert(next.clone()) {
+                        places.push((next, Some((String::from(cavern), *direction))));
+                    }
+                }
+            }
+            places
+        }
+
+        // the hazards the player has sensed or run into.
+        fn hazards(&self) -> BTreeSet<String> {
+            let mut hazards = BTreeSet::new();
+            for cavern in self.knowledge_map.visited() {
+                let knowledge = self.knowledge_map.cavern(cavern).unwrap();
+                hazards.extend(knowledge.found.iter().cloned());
+                if knowledge.observed {
+                    hazards.extend(knowledge.percepts.iter().cloned());
+                }
+            }
+            hazards.remove(WUMPUS);
+            hazards
+        }
+
+        fn hazard_probabilities(
+            &self,
+            hazard: &str,
+            places: &[(Place, Option<(String, Direction)>)],
+        ) -> BTreeMap<Place, f64> {
+            let prior = self.priors.get(hazard).copied().unwrap_or(DEFAULT_PRIOR);
+            let ran_into = |place: &Place| match place {
+                Place::Cavern(cavern) => self
+                    .knowledge_map
+                    .cavern(cavern)
+                    .is_some_and(|k| k.found.iter().any(|h| h == hazard)),
+                Place::Passage(_, _) => false,
+            };
+            // a hazard that moved on is as unknown in the cavern it left as anywhere else.
+            let moved = |place: &Place| self.relocating.contains(hazard) && ran_into(place);
+            let found = |place: &Place| !moved(place) && ran_into(place);
+            let known = |place: &Place| self.is_visited(place) && !moved(place);
+            let mut probabilities = BTreeMap::new();
+            let mut free = BTreeSet::new();
+            for (place, _) in places.iter().filter(|(p, _)| known(p)) {
+                let p = if found(place) { 1.0 } else { 0.0 };
+                probabilities.insert(place.clone(), p);
+            }
+
+            // every place next to a cavern where the hazard was not sensed is free of it, and
+            // next to one where it was, at least one place holds it.
+            let mut constraints: Vec<Vec<Place>> = vec![];
+            for (cavern, percepts) in self.observed() {
+                let neighbours = self.neighbours(cavern);
+                if !percepts.iter().any(|p| p == hazard) {
+                    free.extend(neighbours);
+                } else if !neighbours.iter().any(found) {
+                    constraints.push(neighbours);
+                }
+            }
+            let unknown: Vec<&Place> = places
+                .iter()
+                .map(|(p, _)| p)
+                .filter(|p| !known(p) && !free.contains(*p))
+                .collect();
+            for place in &unknown {
+                probabilities.insert((*place).clone(), prior);
+            }
+            for (place, _) in places.iter().filter(|(p, _)| free.contains(p)) {
+                probabilities.entry(place.clone()).or_insert(0.0);
+            }
+
+            let constraints: Vec<Vec<usize>> = constraints
+                .iter()
+                .map(|neighbours| {
+                    unknown
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, p)| neighbours.contains(p))
+                        .map(|(i, _)| i)
+                        .collect()
+                })
+                .collect();
+            let constrained: BTreeSet<usize> = constraints.iter().flatten().copied().collect();
+            if constrained.is_empty() || constrained.len() > MAX_UNKNOWN_CAVERNS {
+                return probabilities;
+            }
+            let constrained: Vec<usize> = constrained.into_iter().collect();
+            // the bit of each constrained place, and each constraint as the bits it needs one of.
+            let bits: BTreeMap<usize, u32> = constrained
+                .iter()
+                .enumerate()
+                .map(|(bit, i)| (*i, 1 << bit))
+                .collect();
+            let masks: Vec<u32> = constraints
+                .iter()
+                .map(|c| c.iter().map(|i| bits[i]).fold(0, |mask, bit| mask | bit))
+                .collect();
+
+            // weighs every way of placing the hazard among the constrained places.
+            let mut total = 0.0;
+            let mut weights = vec![0.0; constrained.len()];
+            for placement in 0u32..1 << constrained.len() {
+                if !masks.iter().all(|mask| placement & mask != 0) {
+                    continue;
+                }
+                let n_hazards = placement.count_ones();
+                let weight = power(prior, n_hazards)
+                    * power(1.0 - prior, constrained.len() as u32 - n_hazards);
+                total += weight;
+                for (bit, w) in weights.iter_mut().enumerate() {
+                    if placement & (1 << bit) != 0 {
+                        *w += weight;
+                    }
+                }
+            }
+            if total > 0.0 {
+                for (bit, i) in constrained.iter().enumerate() {
+                    probabilities.insert(unknown[*i].clone(), weights[bit] / total);
+                }
+            }
+            probabilities
+        }
+
+        fn wumpus_probabilities(&self) -> BTreeMap<Place, f64> {
+            let mut probabilities = BTreeMap::new();
+            let Some(knowledge) = self.knowledge_map.cavern(self.player_cavern) else {
+                return probabilities;
+            };
+            if !knowledge.observed {
+                return probabilities;
+            }
+            let neighbours = self.neighbours(self.player_cavern);
+            let smelled = knowledge.percepts.iter().any(|p| p == WUMPUS);
+            for neighbour in &neighbours {
+                let p = match smelled {
+                    true => 1.0 / neighbours.len() as f64,
+                    false => 0.0,
+                };
+                probabilities.insert(neighbour.clone(), p);
+            }
+            probabilities
+        }
+    }
+
+    // `base` multiplied by itself `exponent` times, squaring as `f64::powi` does, which only
+    // std has.
+    fn power(mut base: f64, mut exponent: u32) -> f64 {
+        let mut result = 1.0;
+        loop {
+            if exponent & 1 != 0 {
+                result *= base;
+            }
+            exponent /= 2;
+            if exponent == 0 {
+                return result;
+            }
+            base *= base;
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_inference {
+        use super::*;
+        use crate::connection::connection::Connection;
+        use crate::connections::connections::Connections;
+
+        // a row of caverns, a - b - c - d, linked east to west.
+        fn set_up() -> Connections {
+            let mut connections = vec![];
+            for (from, to) in [("a", "b"), ("b", "c"), ("c", "d")] {
+                connections.push(Connection::new(from, to, &Direction::East));
+                connections.push(Connection::new(to, from, &Direction::West));
+            }
+            Connections::new(connections)
+        }
+
+        fn observe(
+            map: &mut KnowledgeMap,
+            connections: &Connections,
+            cavern: &str,
+            percepts: &[&str],
+        ) {
+            map.observe(
+                cavern,
+                connections.report_available_directions(cavern),
+                percepts.iter().map(|p| String::from(*p)).collect(),
+            );
+        }
+
+        // the threats in `cavern`, or behind the passage leading `direction` from it.
+        fn threats(
+            assessments: &[Assessment],
+            cavern: &str,
+            direction: Option<Direction>,
+        ) -> Vec<(String, Danger)> {
+            let assessment = assessments
+                .iter()
+                .find(|a| match direction {
+                    Some(direction) => a.via == Some((String::from(cavern), direction)),
+                    None => a.cavern.as_deref() == Some(cavern),
+                })
+                .unwrap();
+            assessment
+                .threats
+                .iter()
+                .map(|t| (t.name.clone(), t.danger))
+                .collect()
+        }
+
+        #[test]
+        fn test_safe_next_to_no_percepts() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "a", &[]);
+            let assessments = Inference::new(&map, "a").assess();
+            assert_eq!(2, assessments.len());
+            assert_eq!(
+                Some((String::from("a"), Direction::East)),
+                assessments[1].via
+            );
+            assert!(assessments.iter().all(|a| a.is_safe()));
+        }
+
+        #[test]
+        fn test_unwalked_passage_hides_cavern() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "b", &[]);
+            let assessments = Inference::new(&map, "b").assess();
+            assert_eq!(3, assessments.len());
+            assert_eq!(None, assessments[1].cavern);
+            assert_eq!(None, assessments[2].cavern);
+
+            map.link("b", Direction::East, "c");
+            let assessments = Inference::new(&map, "b").assess();
+            let assessment = assessments
+                .iter()
+                .find(|a| a.via == Some((String::from("b"), Direction::East)))
+                .unwrap();
+            assert_eq!(Some(String::from("c")), assessment.cavern);
+        }
+
+        #[test]
+        fn test_certain_with_one_way_out() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "a", &["pits"]);
+            let assessments = Inference::new(&map, "a").assess();
+            assert_eq!(
+                vec![(String::from("pits"), Danger::Certain)],
+                threats(&assessments, "a", Some(Direction::East))
+            );
+        }
+
+        #[test]
+        fn test_possible_between_two_caverns() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "b", &["bats"]);
+            let assessments = Inference::new(&map, "b").prior("bats", 0.5).assess();
+            for direction in [Direction::West, Direction::East] {
+                assert_eq!(
+                    vec![(String::from("bats"), Danger::Possible)],
+                    threats(&assessments, "b", Some(direction))
+                );
+            }
+            // one or both of the two caverns hold bats: 2 of 3 equally likely cases each.
+            let probability = assessments[1].threats[0].probability;
+            assert!((probability - 2.0 / 3.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_free_cavern_narrows_down() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "b", &["pits"]);
+            map.link("b", Direction::East, "c");
+            map.link("c", Direction::East, "d");
+            observe(&mut map, &connections, "d", &[]);
+            let assessments = Inference::new(&map, "d").assess();
+            assert!(assessments
+                .iter()
+                .find(|a| a.cavern.as_deref() == Some("c"))
+                .unwrap()
+                .is_safe());
+            assert_eq!(
+                vec![(String::from("pits"), Danger::Certain)],
+                threats(&assessments, "b", Some(Direction::West))
+            );
+        }
+
+        #[test]
+        fn test_found_hazard_explains_percept() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            map.found("a", "pits");
+            map.link("a", Direction::East, "b");
+            observe(&mut map, &connections, "b", &["pits"]);
+            let assessments = Inference::new(&map, "b").assess();
+            assert_eq!(
+                vec![(String::from("pits"), Danger::Certain)],
+                threats(&assessments, "a", None)
+            );
+            assert_eq!(
+                vec![(String::from("pits"), Danger::Possible)],
+                threats(&assessments, "b", Some(Direction::East))
+            );
+            let probability = assessments[2].threats[0].probability;
+            assert!((probability - DEFAULT_PRIOR).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_moved_hazard_is_unknown() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            map.found("a", "bats");
+            map.link("a", Direction::East, "b");
+            observe(&mut map, &connections, "b", &["bats"]);
+            let assessments = Inference::new(&map, "b")
+                .prior("bats", 0.5)
+                .relocating("bats")
+                .assess();
+            // the bats left a, so they may be in a or beyond c, or both.
+            for (cavern, direction) in [("a", None), ("b", Some(Direction::East))] {
+                assert_eq!(
+                    vec![(String::from("bats"), Danger::Possible)],
+                    threats(&assessments, cavern, direction)
+                );
+            }
+        }
+
+        #[test]
+        fn test_wumpus_next_to_player() {
+            let connections = set_up();
+            let mut map = KnowledgeMap::new();
+            observe(&mut map, &connections, "b", &[WUMPUS]);
+            let assessments = Inference::new(&map, "b").assess();
+            for direction in [Direction::West, Direction::East] {
+                let assessment = assessments
+                    .iter()
+                    .find(|a| a.via == Some((String::from("b"), direction)))
+                    .unwrap();
+                assert_eq!(WUMPUS, assessment.threats[0].name);
+                assert!((assessment.threats[0].probability - 0.5).abs() < 1e-9);
+            }
+        }
+    }
+}
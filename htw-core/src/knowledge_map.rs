@@ -0,0 +1,252 @@
+pub mod knowledge_map {
+    use crate::direction::direction::Direction;
+    use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // the percept of the wumpus, next to the names of the hazards.
+    pub const WUMPUS: &str = "wumpus";
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct CavernKnowledge {
+        // whether the player looked around the cavern, rather than just being carried through.
+        pub observed: bool,
+        // the passages leading out, known once the player has looked around the cavern.
+        pub passages: Vec<Direction>,
+        // what the player sensed nearby the last time they were here, e.g. "pits" or "wumpus".
+        pub percepts: Vec<String>,
+        // the hazards the player ran into here.
+        pub found: Vec<String>,
+    }
+
+    // what the player has found out about the cave, apart from the true connections.
+    #[derive(Debug, Clone, Default)]
+    pub struct KnowledgeMap {
+        visited: Vec<String>,
+        caverns: BTreeMap<String, CavernKnowledge>,
+        links: BTreeMap<(String, Direction), String>,
+    }
+
+    impl KnowledgeMap {
+        pub fn new() -> KnowledgeMap {
+            KnowledgeMap::default()
+        }
+
+        pub(crate) fn visit(&mut self, cavern: &str) {
+            if !self.caverns.contains_key(cavern) {
+                self.visited.push(String::from(cavern));
+                self.caverns
+                    .insert(String::from(cavern), CavernKnowledge::default());
+            }
+        }
+
+        pub(crate) fn observe(
+            &mut self,
+            cavern: &str,
+            passages: Vec<Direction>,
+            percepts: Vec<String>,
+        ) {
+            self.visit(cavern);
+            if let Some(knowledge) = self.caverns.get_mut(cavern) {
+                knowledge.observed = true;
+                knowledge.passages = passages;
+                knowledge.percepts = percepts;
+            }
+        }
+
+        pub(crate) fn found(&mut self, cavern: &str, hazard: &str) {
+            self.visit(cavern);
+            if let Some(knowledge) = self.caverns.get_mut(cavern) {
+                if !knowledge.found.iter().any(|h| h == hazard) {
+                    knowledge.found.push(String::from(hazard));
+                }
+            }
+        }
+
+        // the player walked from one cavern to the other, so the way back is known too.
+        pub(crate) fn link(&mut self, from: &str, direction: Direction, to: &str) {
+            self.links
+                .insert((String::from(from), direction), String::from(to));
+            self.links
+                .insert((String::from(to), direction.opposite()), String::from(from));
+        }
+
+        // the caverns in the order the player first came to them.
+        pub fn visited(&self) -> &[String] {
+            &self.visited
+        }
+
+        pub fn cavern(&self, cavern: &str) -> Option<&CavernKnowledge> {
+            self.caverns.get(cavern)
+        }
+
+        pub fn destination(&self, cavern: &str, direction: Direction) -> Option<&str> {
+            self.links
+                .get(&(String::from(cavern), direction))
+                .map(|to| to.as_str())
+        }
+
+        // lays the known caverns out on a grid around `center`. the passages of a cave need not
+        // fit on a grid, so a cavern whose place is already taken is left out.
+        pub fn layout(&self, center: &str) -> BTreeMap<(i32, i32), String> {
+            let mut places = BTreeMap::new();
+            let mut placed = BTreeSet::new();
+            let mut queue = VecDeque::from([(String::from(center), (0, 0))]);
+            while let Some((cavern, (x, y))) = queue.pop_front() {
+                if placed.contains(&cavern) || places.contains_key(&(x, y)) {
+                    continue;
+                }
+                placed.insert(cavern.clone());
+                for direction in DIRECTIONS {
+                    if let Some(next) = self.destination(&cavern, direction) {
+                        let (dx, dy) = offset(direction);
+                        queue.push_back((String::from(next), (x + dx, y + dy)));
+                    }
+                }
+                places.insert((x, y), cavern);
+            }
+            places
+        }
+
+        // draws the map around `center` in a box of `width` by `height` characters:
+        // `@` is `center`, `o` a visited cavern, `!` one where danger was sensed nearby,
+        // and `?` a passage nobody has walked yet.
+        pub fn draw(&self, center: &str, width: u16, height: u16) -> Vec<String> {
+            let (width, height) = (width as i32, height as i32);
+            let mut canvas = vec![vec![' '; width as usize]; height as usize];
+            let mut put = |x: i32, y: i32, c: char| {
+                let (x, y) = (x + width / 2, y + height / 2);
+                if (0..width).contains(&x) && (0..height).contains(&y) {
+                    canvas[y as usize][x as usize] = c;
+                }
+            };
+            for ((x, y), cavern) in self.layout(center) {
+                let (x, y) = (x * 4, y * 2);
+                let knowledge = self.caverns.get(&cavern).cloned().unwrap_or_default();
+                for direction in &knowledge.passages {
+                    let (dx, dy) = offset(*direction);
+                    let walked = self.destination(&cavern, *direction).is_some();
+                    match direction {
+                        Direction::East | Direction::West => {
+                            put(x + dx, y, '-');
+                            put(x + 2 * dx, y, if walked { '-' } else { '?' });
+                        }
+                        Direction::North | Direction::South => {
+                            put(x, y + dy, if walked { '|' } else { '?' });
+                        }
+                    }
+                }
+                let mark = match (cavern == center, knowledge.percepts.is_empty()) {
+                    (true, _) => '@',
+                    (false, true) => 'o',
+                    (false, false) => '!',
+                };
+                put(x, y, mark);
+            }
+            canvas
+                .into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect()
+        }
+    }
+
+    fn offset(direction: Direction) -> (i32, i32) {
+        match direction {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_knowledge_map {
+        use super::*;
+
+        fn set_up() -> KnowledgeMap {
+            let mut map = KnowledgeMap::new();
+            map.observe("a", vec![Direction::East, Direction::South], vec![]);
+            map.link("a", Direction::East, "b");
+            map.observe(
+                "b",
+                vec![Direction::West, Direction::North],
+                vec![String::from(WUMPUS)],
+            );
+            map
+        }
+
+        #[test]
+        fn test_observe() {
+            let map = set_up();
+            assert_eq!(&[String::from("a"), String::from("b")], map.visited());
+            assert_eq!(
+                Some(&CavernKnowledge {
+                    observed: true,
+                    passages: vec![Direction::West, Direction::North],
+                    percepts: vec![String::from(WUMPUS)],
+                    found: vec![],
+                }),
+                map.cavern("b")
+            );
+            assert_eq!(None, map.cavern("c"));
+        }
+
+        #[test]
+        fn test_visit_keeps_what_was_observed() {
+            let mut map = set_up();
+            map.visit("a");
+            assert_eq!(2, map.cavern("a").unwrap().passages.len());
+            assert_eq!(2, map.visited().len());
+        }
+
+        #[test]
+        fn test_found() {
+            let mut map = set_up();
+            map.found("c", "pits");
+            map.found("c", "pits");
+            let knowledge = map.cavern("c").unwrap();
+            assert!(!knowledge.observed);
+            assert_eq!(vec![String::from("pits")], knowledge.found);
+        }
+
+        #[test]
+        fn test_link_both_ways() {
+            let map = set_up();
+            assert_eq!(Some("b"), map.destination("a", Direction::East));
+            assert_eq!(Some("a"), map.destination("b", Direction::West));
+            assert_eq!(None, map.destination("a", Direction::South));
+        }
+
+        #[test]
+        fn test_layout() {
+            let map = set_up();
+            let places = map.layout("b");
+            assert_eq!(Some(&String::from("b")), places.get(&(0, 0)));
+            assert_eq!(Some(&String::from("a")), places.get(&(-1, 0)));
+            assert_eq!(2, places.len());
+        }
+
+        #[test]
+        fn test_draw() {
+            let map = set_up();
+            assert_eq!(
+                vec![
+                    "         ",
+                    "        ?",
+                    "    @---!",
+                    "    ?    ",
+                    "         ",
+                ],
+                map.draw("a", 9, 5)
+            );
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// the rules, the cave and the commands of the game, without any input or output. it only needs
+// an allocator, so the command line, a server or the bindings can all be built on it.
+#![no_std]
+#![allow(clippy::module_inception)]
+extern crate alloc;
+
+pub mod agent;
+mod commands;
+pub mod connection;
+pub mod connections;
+pub mod difficulty;
+pub mod direction;
+pub mod game_builder;
+pub mod game_config;
+pub mod game_setup;
+pub mod hazards;
+pub mod htw_error;
+pub mod htw_game;
+pub mod htw_message;
+pub mod inference;
+pub mod knowledge_map;
+pub mod map_export;
+pub mod map_file;
+pub mod wumpus_env;
+use crate::direction::direction::Direction;
+use crate::htw_game::htw_game::{Caverns, GameStatus};
+pub use crate::htw_message::htw_message::RecordingMessageReceiver;
+use crate::inference::inference::Assessment;
+use crate::knowledge_map::knowledge_map::KnowledgeMap;
+use crate::map_export::map_export::MapFormat;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub trait HtwMessageReceiver {
+    fn no_passage(&self);
+    fn hear_bats(&self);
+    fn hear_pit(&self);
+    fn smell_wumpus(&self);
+    fn passage(&self, direction: &Direction);
+    fn no_arrows(&self);
+    fn arrow_shot(&self);
+    fn player_shoots_self_in_back(&self);
+    fn player_kills_wumpus(&self);
+    fn player_shoots_wall(&self);
+    fn arrows_found(&self, arrows_found: u32);
+    fn fell_in_pit(&self);
+    fn player_moves_to_wumpus(&self);
+    fn wumpus_moves_to_player(&self);
+    fn bats_transport(&self);
+    fn see_webs(&self);
+    fn caught_in_web(&self);
+    fn player_stuck(&self);
+    fn smell_gas(&self);
+    fn breathe_gas(&self);
+    fn player_rests(&self, hit_points_recovered: u32);
+    fn you_die(&self);
+}
+
+pub trait HuntTheWumpus {
+    fn get_player_cavern(&self) -> &str;
+    fn get_wumpus_cavern(&self) -> &str;
+    fn get_quiver(&self) -> u32;
+    fn get_hit_points(&self) -> u32;
+    fn get_max_hit_points(&self) -> u32;
+    fn get_status(&self) -> &GameStatus;
+    // whether the last turn was broken into by a hazard, a wound or the end of the game.
+    fn was_interrupted(&self) -> bool;
+    fn report_status(&self);
+    fn execute_command(&mut self);
+    fn make_rest_command(&mut self);
+    fn make_shoot_command(&mut self, direction: Direction);
+    fn make_move_command(&mut self, direction: Direction);
+    fn caverns(&self) -> &Caverns;
+    // what the player has found out about the cave so far.
+    fn knowledge_map(&self) -> &KnowledgeMap;
+    // what may lie in the caverns the player knows of, judging by what they have sensed.
+    fn assess_caverns(&self) -> Vec<Assessment>;
+    // the steps to a cavern the player knows, each a direction and the cavern it leads to.
+    fn route_to(&self, cavern: &str) -> Option<Vec<(Direction, String)>>;
+    // the whole cave, with the player, the wumpus, the hazards and the arrows marked.
+    fn export_map(&self, format: MapFormat) -> String;
+}
@@ -0,0 +1,361 @@
+pub mod map_export {
+    use crate::connections::connections::{sorted, Connections};
+    use crate::htw_game::htw_game::{ArrowsIn, Caverns};
+    use crate::Direction;
+    use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::fmt::Write;
+
+    // the room a cavern takes in the SVG drawing, and the radius of its circle.
+    const CELL_WIDTH: i32 = 240;
+    const CELL_HEIGHT: i32 = 130;
+    const RADIUS: i32 = 18;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MapFormat {
+        Dot,
+        Svg,
+    }
+
+    // what to highlight on an exported map.
+    #[derive(Debug, Clone, Default)]
+    pub struct MapMarks {
+        pub player: Option<String>,
+        pub wumpus: Option<String>,
+        // the names of the hazards in each cavern.
+        pub hazards: BTreeMap<String, Vec<String>>,
+        pub arrows: ArrowsIn,
+    }
+
+    impl MapMarks {
+        fn tags(&self, cavern: &str) -> Vec<String> {
+            let mut tags = vec![];
+            if self.player.as_deref() == Some(cavern) {
+                tags.push(String::from("player"));
+            }
+            if self.wumpus.as_deref() == Some(cavern) {
+                tags.push(String::from("wumpus"));
+            }
+            tags.extend(self.hazards.get(cavern).into_iter().flatten().cloned());
+            match self.arrows.get(cavern) {
+                Some(1) => tags.push(String::from("1 arrow")),
+                Some(n) if *n > 1 => tags.push(format!("{} arrows", n)),
+                _ => {}
+            }
+            tags
+        }
+
+        fn color(&self, cavern: &str) -> &str {
+            let hazards = self.hazards.get(cavern);
+            let has = |name: &str| hazards.is_some_and(|h| h.iter().any(|n| n == name));
+            if self.wumpus.as_deref() == Some(cavern) {
+                "tomato"
+            } else if self.player.as_deref() == Some(cavern) {
+                "gold"
+            } else if has("pits") {
+                "gray"
+            } else if has("bats") {
+                "plum"
+            } else if hazards.is_some_and(|h| !h.is_empty()) {
+                "orange"
+            } else if self.arrows.get(cavern).is_some_and(|n| *n > 0) {
+                "lightblue"
+            } else {
+                "white"
+            }
+        }
+    }
+
+    impl Connections {
+        pub fn export(&self, format: MapFormat, caverns: &Caverns, marks: &MapMarks) -> String {
+            match format {
+                MapFormat::Dot => self.to_dot(caverns, marks),
+                MapFormat::Svg => self.to_svg(caverns, marks),
+            }
+        }
+
+        // a Graphviz digraph with a node per cavern and an edge per passage, labelled with
+        // its direction.
+        pub fn to_dot(&self, caverns: &Caverns, marks: &MapMarks) -> String {
+            let mut dot = String::from("digraph cave {\n    node [shape=ellipse, style=filled];\n");
+            for cavern in sorted(caverns) {
+                let mut label = escape_dot(cavern);
+                for tag in marks.tags(cavern) {
+                    label.push_str("\\n");
+                    label.push_str(&escape_dot(&tag));
+                }
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" [label=\"{}\", fillcolor=\"{}\"];",
+                    escape_dot(cavern),
+                    label,
+                    marks.color(cavern)
+                );
+            }
+            for c in &self.connections {
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    escape_dot(c.from()),
+                    escape_dot(c.to()),
+                    &c.direction().name()[..1]
+                );
+            }
+            dot.push_str("}\n");
+            dot
+        }
+
+        // draws the cave without Graphviz: the caverns are laid out on a grid, following the
+        // directions of the passages where they fit.
+        pub fn to_svg(&self, caverns: &Caverns, marks: &MapMarks) -> String {
+            let places = self.layout(caverns);
+            let width = places.values().map(|(x, _)| x + 1).max().unwrap_or(1) * CELL_WIDTH;
+            let height = places.values().map(|(_, y)| y + 1).max().unwrap_or(1) * CELL_HEIGHT;
+            let center = |cavern: &str| {
+                let (x, y) = places[cavern];
+                (
+                    (x * CELL_WIDTH + CELL_WIDTH / 2) as f64,
+                    (y * CELL_HEIGHT + CELL_HEIGHT / 3) as f64,
+                )
+            };
+
+            let mut svg = String::new();
+            let _ = writeln!(
+                svg,
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+                 viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"11\">",
+                width, height
+            );
+            svg.push_str(
+                "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+                 markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+                 <path d=\"M 0 0 L 10 5 L 0 10 z\"/></marker></defs>\n",
+            );
+            let _ = writeln!(
+                svg,
+                "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>",
+                width, height
+            );
+            for c in &self.connections {
+                let ((x1, y1), (x2, y2)) = (center(c.from()), center(c.to()));
+                let (dx, dy) = (x2 - x1, y2 - y1);
+                let length = libm::sqrt(dx * dx + dy * dy).max(1.0);
+                let (ux, uy) = (dx / length, dy / length);
+                // the passages each way run side by side.
+                let (nx, ny) = (-uy * 4.0, ux * 4.0);
+                let r = RADIUS as f64;
+                let (ax, ay) = (x1 + ux * r + nx, y1 + uy * r + ny);
+                let (bx, by) = (x2 - ux * r + nx, y2 - uy * r + ny);
+                let _ = writeln!(
+                    svg,
+                    "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"black\" \
+                     marker-end=\"url(#arrow)\"/>",
+                    ax, ay, bx, by
+                );
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"dimgray\" text-anchor=\"middle\">{}</text>",
+                    ax + (bx - ax) * 0.3 + nx * 2.5,
+                    ay + (by - ay) * 0.3 + ny * 2.5 + 4.0,
+                    &c.direction().name()[..1]
+                );
+            }
+            for cavern in sorted(caverns) {
+                let (x, y) = center(cavern);
+                let _ = writeln!(
+                    svg,
+                    "<g><title>{}</title><circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" \
+                     stroke=\"black\"/>",
+                    escape_xml(cavern),
+                    x,
+                    y,
+                    RADIUS,
+                    marks.color(cavern)
+                );
+                let mut lines = split_name(cavern);
+                let tag_line = lines.len();
+                let tags = marks.tags(cavern);
+                if !tags.is_empty() {
+                    lines.push(tags.join(", "));
+                }
+                for (i, line) in lines.iter().enumerate() {
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\"{}>{}</text>",
+                        x,
+                        y + (RADIUS + 14 + 13 * i as i32) as f64,
+                        if i == tag_line {
+                            " font-style=\"italic\""
+                        } else {
+                            ""
+                        },
+                        escape_xml(line)
+                    );
+                }
+                svg.push_str("</g>\n");
+            }
+            svg.push_str("</svg>\n");
+            svg
+        }
+
+        // places each cavern on a grid cell, the next one along a passage in the passage's
+        // direction, or in the nearest free cell when that is taken.
+        pub fn layout(&self, caverns: &Caverns) -> BTreeMap<String, (i32, i32)> {
+            let mut places: BTreeMap<String, (i32, i32)> = BTreeMap::new();
+            let mut taken: BTreeSet<(i32, i32)> = BTreeSet::new();
+            for start in sorted(caverns) {
+                if places.contains_key(start) {
+                    continue;
+                }
+                let origin = nearest_free(&taken, (0, 0));
+                let mut queue = VecDeque::from([(start.as_str(), origin)]);
+                while let Some((cavern, cell)) = queue.pop_front() {
+                    if places.contains_key(cavern) {
+                        continue;
+                    }
+                    let cell = nearest_free(&taken, cell);
+                    taken.insert(cell);
+                    places.insert(String::from(cavern), cell);
+                    for c in &self.connections {
+                        if c.from() == cavern && caverns.contains(c.to()) {
+                            let (dx, dy) = offset(*c.direction());
+                            queue.push_back((c.to(), (cell.0 + dx, cell.1 + dy)));
+                        }
+                    }
+                }
+            }
+            let min_x = places.values().map(|(x, _)| *x).min().unwrap_or(0);
+            let min_y = places.values().map(|(_, y)| *y).min().unwrap_or(0);
+            for (x, y) in places.values_mut() {
+                *x -= min_x;
+                *y -= min_y;
+            }
+            places
+        }
+    }
+
+    fn offset(direction: Direction) -> (i32, i32) {
+        match direction {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    // the free cell closest to `cell`, searching rings of growing size around it.
+    fn nearest_free(taken: &BTreeSet<(i32, i32)>, cell: (i32, i32)) -> (i32, i32) {
+        let (x, y) = cell;
+        for ring in 0.. {
+            for dy in -ring..=ring {
+                for dx in -ring..=ring {
+                    let candidate = (x + dx, y + dy);
+                    let on_ring = dx.abs() == ring || dy.abs() == ring;
+                    if on_ring && !taken.contains(&candidate) {
+                        return candidate;
+                    }
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    // cavern names are long, so they are written on two lines.
+    fn split_name(name: &str) -> Vec<String> {
+        let words: Vec<&str> = name.split_whitespace().collect();
+        if words.len() < 4 {
+            return vec![String::from(name)];
+        }
+        let half = words.len() / 2;
+        vec![words[..half].join(" "), words[half..].join(" ")]
+    }
+
+    fn escape_dot(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn escape_xml(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    #[cfg(test)]
+    mod tests_for_map_export {
+        use super::*;
+        use crate::connection::connection::Connection;
+
+        fn set_up() -> (Connections, Caverns, MapMarks) {
+            let connections = Connections::new(vec![
+                Connection::new("cavern", "cavern_n", &Direction::North),
+                Connection::new("cavern_n", "cavern", &Direction::South),
+                Connection::new("cavern", "cavern_e", &Direction::East),
+                Connection::new("cavern_e", "cavern", &Direction::West),
+            ]);
+            let caverns = Caverns::from([
+                String::from("cavern"),
+                String::from("cavern_n"),
+                String::from("cavern_e"),
+                String::from("lost \"cavern\""),
+            ]);
+            let marks = MapMarks {
+                player: Some(String::from("cavern")),
+                wumpus: Some(String::from("cavern_e")),
+                hazards: BTreeMap::from([(String::from("cavern_n"), vec![String::from("pits")])]),
+                arrows: BTreeMap::from([(String::from("cavern_n"), 2)]),
+            };
+            (connections, caverns, marks)
+        }
+
+        #[test]
+        fn test_to_dot() {
+            let (connections, caverns, marks) = set_up();
+            let dot = connections.to_dot(&caverns, &marks);
+            assert!(dot.starts_with("digraph cave {\n"));
+            assert!(
+                dot.contains("    \"cavern\" [label=\"cavern\\nplayer\", fillcolor=\"gold\"];\n")
+            );
+            assert!(dot.contains(
+                "    \"cavern_n\" [label=\"cavern_n\\npits\\n2 arrows\", fillcolor=\"gray\"];\n"
+            ));
+            assert!(dot.contains("    \"cavern_e\" -> \"cavern\" [label=\"W\"];\n"));
+            assert!(dot.contains("\"lost \\\"cavern\\\"\""));
+            assert!(dot.ends_with("}\n"));
+        }
+
+        #[test]
+        fn test_to_svg() {
+            let (connections, caverns, marks) = set_up();
+            let svg = connections.export(MapFormat::Svg, &caverns, &marks);
+            assert!(svg.starts_with("<svg "));
+            assert_eq!(4, svg.matches("<circle").count());
+            assert_eq!(4, svg.matches("<line").count());
+            assert!(svg.contains("fill=\"tomato\""));
+            assert!(svg.contains("lost &quot;cavern&quot;"));
+        }
+
+        #[test]
+        fn test_layout() {
+            let (connections, caverns, _) = set_up();
+            let places = connections.layout(&caverns);
+            let (x, y) = places["cavern"];
+            assert_eq!((x, y - 1), places["cavern_n"]);
+            assert_eq!((x + 1, y), places["cavern_e"]);
+            let cells: BTreeSet<&(i32, i32)> = places.values().collect();
+            assert_eq!(4, cells.len());
+        }
+
+        #[test]
+        fn test_split_name() {
+            assert_eq!(vec!["a dark cave"], split_name("a dark cave"));
+            assert_eq!(
+                vec!["A cold round", "chamber with guano"],
+                split_name("A cold round chamber with guano")
+            );
+        }
+    }
+}
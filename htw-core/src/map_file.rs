@@ -0,0 +1,167 @@
+pub mod map_file {
+    use crate::connection::connection::Connection;
+    use crate::connections::connections::Connections;
+    use crate::direction::direction::Direction;
+    use crate::htw_error::htw_error::HtwError;
+    use crate::htw_game::htw_game::Caverns;
+    use alloc::collections::BTreeSet;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // a map file lists one passage per line, as `from | direction | to`, and a passage that can
+    // be walked back as `from | direction | to | direction back`. a line holding only a name
+    // adds a cavern, and lines starting with `#` are comments. the passages are taken as
+    // written, so a map may not be valid, see `load_map`.
+    pub fn parse_map(text: &str) -> Result<(Caverns, Connections), HtwError> {
+        let mut caverns = BTreeSet::new();
+        let mut connections = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('|').map(|f| f.trim()).collect();
+            let invalid_line =
+                |reason: &str| HtwError::InvalidMapLine(index + 1, String::from(reason));
+            let direction = |field: &str| {
+                Direction::parse(field).ok_or_else(|| invalid_line("unknown direction"))
+            };
+            match fields[..] {
+                [cavern] => {
+                    caverns.insert(String::from(cavern));
+                }
+                [from, _, to] | [from, _, to, _] if from.is_empty() || to.is_empty() => {
+                    return Err(invalid_line("missing cavern name"));
+                }
+                [from, there, to] => {
+                    caverns.insert(String::from(from));
+                    caverns.insert(String::from(to));
+                    connections.push(Connection::new(from, to, &direction(there)?));
+                }
+                [from, there, to, back] => {
+                    caverns.insert(String::from(from));
+                    caverns.insert(String::from(to));
+                    connections.push(Connection::new(from, to, &direction(there)?));
+                    connections.push(Connection::new(to, from, &direction(back)?));
+                }
+                _ => return Err(invalid_line("expected `from | direction | to`")),
+            }
+        }
+        if caverns.is_empty() {
+            return Err(HtwError::NoCaverns);
+        }
+        Ok((caverns, Connections::new(connections)))
+    }
+
+    // parses a map file and checks the passages make a valid map, see
+    // `Connections::validate`.
+    pub fn load_map(text: &str) -> Result<(Caverns, Connections), HtwError> {
+        let (caverns, connections) = parse_map(text)?;
+        connections.validate(&caverns)?;
+        Ok((caverns, connections))
+    }
+
+    #[cfg(test)]
+    mod tests_for_map_file {
+        use super::*;
+        use crate::htw_error::htw_error::MapViolation;
+
+        #[test]
+        fn test_parse_map() {
+            let text = "# a small cave\n\
+                        cavern | north | cavern_n\n\
+                        cavern | e | cavern_e | w\n\
+                        \n\
+                        cavern_x\n";
+            let (caverns, connections) = parse_map(text).unwrap();
+            assert_eq!(4, caverns.len());
+            assert!(caverns.contains("cavern_x"));
+            assert_eq!(3, connections.connections.len());
+            assert_eq!(
+                Some(String::from("cavern")),
+                connections.find_destination("cavern_e", &Direction::West)
+            );
+            // written one way only.
+            assert_eq!(
+                None,
+                connections.find_destination("cavern_n", &Direction::South)
+            );
+        }
+
+        #[test]
+        fn test_parse_map_invalid_line() {
+            assert_eq!(
+                Some(HtwError::InvalidMapLine(
+                    2,
+                    String::from("unknown direction")
+                )),
+                parse_map("cavern\ncavern | up | cavern_n").err()
+            );
+            assert_eq!(
+                Some(HtwError::InvalidMapLine(
+                    1,
+                    String::from("unknown direction")
+                )),
+                parse_map("cavern | n | cavern_n | down").err()
+            );
+            assert_eq!(
+                Some(HtwError::InvalidMapLine(
+                    1,
+                    String::from("expected `from | direction | to`")
+                )),
+                parse_map("cavern | north").err()
+            );
+        }
+
+        #[test]
+        fn test_load_map() {
+            let (caverns, connections) =
+                load_map("cavern | n | cavern_n | s\ncavern | e | cavern_e | w").unwrap();
+            assert_eq!(3, caverns.len());
+            assert_eq!(4, connections.connections.len());
+            assert!(load_map("cavern").is_ok());
+        }
+
+        #[test]
+        fn test_load_invalid_map() {
+            assert_eq!(
+                Some(HtwError::InvalidMap(vec![
+                    MapViolation::DuplicateDirection {
+                        cavern: String::from("cavern"),
+                        direction: Direction::North,
+                    },
+                    MapViolation::Unreachable(String::from("cavern_x")),
+                ])),
+                load_map("cavern | n | cavern_n | s\ncavern | n | cavern_e | s\ncavern_x").err()
+            );
+            assert_eq!(
+                Some(HtwError::InvalidMap(vec![
+                    MapViolation::MissingReverse {
+                        from: String::from("cavern"),
+                        to: String::from("cavern_n"),
+                        direction: Direction::North,
+                    },
+                    MapViolation::WrongReverseDirection {
+                        from: String::from("cavern"),
+                        to: String::from("cavern_e"),
+                        direction: Direction::East,
+                        reverse: Direction::South,
+                    },
+                    MapViolation::WrongReverseDirection {
+                        from: String::from("cavern_e"),
+                        to: String::from("cavern"),
+                        direction: Direction::South,
+                        reverse: Direction::East,
+                    },
+                ])),
+                load_map("cavern | n | cavern_n\ncavern | e | cavern_e | s").err()
+            );
+        }
+
+        #[test]
+        fn test_parse_empty_map() {
+            assert_eq!(Some(HtwError::NoCaverns), parse_map("# nothing here").err());
+        }
+    }
+}
@@ -0,0 +1,370 @@
+pub mod wumpus_env {
+    use crate::direction::direction::Direction;
+    use crate::game_setup::game_setup::GameSetup;
+    use crate::htw_error::htw_error::HtwError;
+    use crate::htw_game::htw_game::GameStatus;
+    use crate::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+    use crate::HuntTheWumpus;
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+    pub const N_ACTIONS: usize = 9;
+    // the percepts, the passages, the hit points, the most hit points and the arrows.
+    pub const OBSERVATION_SIZE: usize = 12;
+
+    // the moves and the shots go north, south, east and west, in that order, then rest.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Action {
+        Move(Direction),
+        Shoot(Direction),
+        Rest,
+    }
+
+    impl Action {
+        pub fn from_index(index: usize) -> Option<Action> {
+            match index {
+                0..=3 => Some(Action::Move(DIRECTIONS[index])),
+                4..=7 => Some(Action::Shoot(DIRECTIONS[index - 4])),
+                8 => Some(Action::Rest),
+                _ => None,
+            }
+        }
+
+        pub fn index(&self) -> usize {
+            let position = |direction| DIRECTIONS.iter().position(|d| *d == direction);
+            match self {
+                Action::Move(direction) => position(*direction).unwrap_or(0),
+                Action::Shoot(direction) => 4 + position(*direction).unwrap_or(0),
+                Action::Rest => 8,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    pub struct Percepts {
+        pub wumpus: bool,
+        pub pits: bool,
+        pub bats: bool,
+        pub webs: bool,
+        pub gas: bool,
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    pub struct EnvObservation {
+        pub percepts: Percepts,
+        // whether there is a passage north, south, east and west.
+        pub passages: [bool; 4],
+        pub hit_points: u32,
+        pub max_hit_points: u32,
+        pub arrows: u32,
+    }
+
+    impl EnvObservation {
+        fn new(game: &dyn HuntTheWumpus, messages: &[HtwMessage]) -> EnvObservation {
+            let mut observation = EnvObservation {
+                hit_points: game.get_hit_points(),
+                max_hit_points: game.get_max_hit_points(),
+                arrows: game.get_quiver(),
+                ..EnvObservation::default()
+            };
+            let percepts = &mut observation.percepts;
+            for message in messages {
+                match message {
+                    HtwMessage::SmellWumpus => percepts.wumpus = true,
+                    HtwMessage::HearPit => percepts.pits = true,
+                    HtwMessage::HearBats => percepts.bats = true,
+                    HtwMessage::SeeWebs => percepts.webs = true,
+                    HtwMessage::SmellGas => percepts.gas = true,
+                    HtwMessage::Passage(direction) => {
+                        let position = DIRECTIONS.iter().position(|d| d == direction);
+                        if let Some(i) = position {
+                            observation.passages[i] = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            observation
+        }
+
+        // the percepts and the passages as 0 or 1, then the hit points, the most hit points
+        // and the arrows as they are.
+        pub fn encode(&self) -> [f32; OBSERVATION_SIZE] {
+            let bit = |b: bool| if b { 1.0 } else { 0.0 };
+            let p = &self.percepts;
+            [
+                bit(p.wumpus),
+                bit(p.pits),
+                bit(p.bats),
+                bit(p.webs),
+                bit(p.gas),
+                bit(self.passages[0]),
+                bit(self.passages[1]),
+                bit(self.passages[2]),
+                bit(self.passages[3]),
+                self.hit_points as f32,
+                self.max_hit_points as f32,
+                self.arrows as f32,
+            ]
+        }
+    }
+
+    // what each step earns. the rewards add up, e.g. the last step of a won game earns
+    // `step` and `win`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Rewards {
+        pub win: f64,
+        pub death: f64,
+        pub step: f64,
+        // for each hit point lost.
+        pub damage: f64,
+        pub arrow_shot: f64,
+        // for the first visit to a cavern.
+        pub new_cavern: f64,
+        // for walking into a wall or shooting without arrows.
+        pub wasted_turn: f64,
+    }
+
+    impl Default for Rewards {
+        fn default() -> Rewards {
+            Rewards {
+                win: 1.0,
+                death: -1.0,
+                step: -0.01,
+                damage: 0.0,
+                arrow_shot: 0.0,
+                new_cavern: 0.0,
+                wasted_turn: 0.0,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct EnvConfig {
+        pub rewards: Rewards,
+        // the episode is cut off after this many steps.
+        pub max_steps: u32,
+    }
+
+    impl Default for EnvConfig {
+        fn default() -> EnvConfig {
+            EnvConfig {
+                rewards: Rewards::default(),
+                max_steps: 1000,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct StepInfo {
+        pub status: GameStatus,
+        pub steps: u32,
+        // the episode ran out of steps before the game was over.
+        pub truncated: bool,
+        pub messages: Vec<HtwMessage>,
+    }
+
+    // a game for reinforcement learning, played one action at a time. the game messages are
+    // only logged, so nothing is printed.
+    pub struct WumpusEnv {
+        setup: GameSetup,
+        config: EnvConfig,
+        game: Box<dyn HuntTheWumpus>,
+        log: HtwMessageLog,
+        steps: u32,
+    }
+
+    impl WumpusEnv {
+        // the environment starts out reset with `seed`.
+        pub fn new(setup: GameSetup, config: EnvConfig, seed: u64) -> Result<WumpusEnv, HtwError> {
+            let log = HtwMessageLog::new();
+            let game = setup.build_from_seed(seed, Box::new(log.clone()))?;
+            Ok(WumpusEnv {
+                setup,
+                config,
+                game,
+                log,
+                steps: 0,
+            })
+        }
+
+        // starts the game that `--seed` would.
+        pub fn reset(&mut self, seed: u64) -> Result<EnvObservation, HtwError> {
+            self.game = self
+                .setup
+                .build_from_seed(seed, Box::new(self.log.clone()))?;
+            self.steps = 0;
+            Ok(self.observe())
+        }
+
+        // what the player sees where they are.
+        pub fn observe(&self) -> EnvObservation {
+            self.log.take_messages();
+            self.game.report_status();
+            EnvObservation::new(self.game.as_ref(), &self.log.take_messages())
+        }
+
+        pub fn step(&mut self, action: Action) -> (EnvObservation, f64, bool, StepInfo) {
+            let rewards = &self.config.rewards;
+            let hit_points = self.game.get_hit_points();
+            let visited = self.game.knowledge_map().visited().len();
+            let arrows = self.game.get_quiver();
+            match action {
+                Action::Move(direction) => self.game.make_move_command(direction),
+                Action::Shoot(direction) => self.game.make_shoot_command(direction),
+                Action::Rest => self.game.make_rest_command(),
+            }
+            self.game.execute_command();
+            self.steps += 1;
+            let messages = self.log.take_messages();
+
+            let mut reward = rewards.step;
+            reward += rewards.damage * hit_points.saturating_sub(self.game.get_hit_points()) as f64;
+            if matches!(action, Action::Shoot(_)) && arrows > 0 {
+                reward += rewards.arrow_shot;
+            }
+            if self.game.knowledge_map().visited().len() > visited {
+                reward += rewards.new_cavern;
+            }
+            if messages
+                .iter()
+                .any(|m| matches!(m, HtwMessage::NoPassage | HtwMessage::NoArrows))
+            {
+                reward += rewards.wasted_turn;
+            }
+            let status = self.game.get_status().clone();
+            match status {
+                GameStatus::Won => reward += rewards.win,
+                GameStatus::Lost(_) => reward += rewards.death,
+                GameStatus::Playing => {}
+            }
+            let truncated = status == GameStatus::Playing && self.steps >= self.config.max_steps;
+            let done = status != GameStatus::Playing || truncated;
+            let observation = EnvObservation::new(self.game.as_ref(), &messages);
+            let info = StepInfo {
+                status,
+                steps: self.steps,
+                truncated,
+                messages,
+            };
+            (observation, reward, done, info)
+        }
+
+        pub fn game(&self) -> &dyn HuntTheWumpus {
+            self.game.as_ref()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_wumpus_env {
+        use super::*;
+        use crate::connection::connection::Connection;
+        use crate::connections::connections::Connections;
+        use crate::difficulty::difficulty::Difficulty;
+        use crate::game_builder::game_builder::GameBuilder;
+        use alloc::collections::BTreeSet;
+        use alloc::string::String;
+        use alloc::vec;
+
+        // the player in a, the wumpus in b to the east, and one arrow.
+        fn set_up(config: EnvConfig) -> WumpusEnv {
+            let caverns = BTreeSet::from([String::from("a"), String::from("b")]);
+            let connections = Connections::new(vec![
+                Connection::new("a", "b", &Direction::East),
+                Connection::new("b", "a", &Direction::West),
+            ]);
+            let log = HtwMessageLog::new();
+            let game = GameBuilder::new(caverns)
+                .connections(connections)
+                .player_cavern("a")
+                .wumpus_cavern("b")
+                .quiver(1)
+                .message_receiver(Box::new(log.clone()))
+                .seed(0)
+                .build()
+                .unwrap();
+            WumpusEnv {
+                setup: GameSetup::new(Difficulty::Easy.preset()),
+                config,
+                game,
+                log,
+                steps: 0,
+            }
+        }
+
+        #[test]
+        fn test_action_index() {
+            for index in 0..N_ACTIONS {
+                assert_eq!(index, Action::from_index(index).unwrap().index());
+            }
+            assert_eq!(Some(Action::Shoot(Direction::East)), Action::from_index(6));
+            assert_eq!(None, Action::from_index(N_ACTIONS));
+        }
+
+        #[test]
+        fn test_observe() {
+            let env = set_up(EnvConfig::default());
+            let observation = env.observe();
+            assert_eq!(
+                [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 10.0, 10.0, 1.0],
+                observation.encode()
+            );
+        }
+
+        #[test]
+        fn test_step_wins() {
+            let mut env = set_up(EnvConfig::default());
+            let (observation, reward, done, info) = env.step(Action::Shoot(Direction::East));
+            assert!(done);
+            assert_eq!(1.0 - 0.01, reward);
+            assert_eq!(GameStatus::Won, info.status);
+            assert!(!info.truncated);
+            assert_eq!(1, observation.arrows);
+        }
+
+        #[test]
+        fn test_step_into_wall() {
+            let mut config = EnvConfig::default();
+            config.rewards.wasted_turn = -0.5;
+            let mut env = set_up(config);
+            let (_, reward, done, info) = env.step(Action::Move(Direction::North));
+            assert!(!done);
+            assert_eq!(-0.51, reward);
+            assert_eq!(Some(&HtwMessage::NoPassage), info.messages.first());
+        }
+
+        #[test]
+        fn test_step_truncates() {
+            let config = EnvConfig {
+                max_steps: 1,
+                ..EnvConfig::default()
+            };
+            let mut env = set_up(config);
+            let (_, _, done, info) = env.step(Action::Move(Direction::North));
+            assert!(done);
+            assert!(info.truncated);
+            assert_eq!(GameStatus::Playing, info.status);
+            assert_eq!(1, info.steps);
+        }
+
+        #[test]
+        fn test_reset_replays_seed() {
+            let mut setup = GameSetup::new(Difficulty::Normal.preset());
+            setup.caverns = Some(20);
+            let mut env = WumpusEnv::new(setup, EnvConfig::default(), 3).unwrap();
+            let cavern = String::from(env.game().get_player_cavern());
+            let observation = env.observe();
+            env.step(Action::Rest);
+            env.reset(4).unwrap();
+            assert_eq!(observation, env.reset(3).unwrap());
+            assert_eq!(cavern, env.game().get_player_cavern());
+            assert_eq!(20, env.game().caverns().len());
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// writes include/htw.h from the `extern "C"` functions in src/lib.rs.
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("the header cannot be generated")
+        .write_to_file(format!("{}/include/htw.h", crate_dir));
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
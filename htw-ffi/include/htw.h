@@ -0,0 +1,181 @@
+/*
+ * Hunt the Wumpus for C and C++. Make a game with htw_game_new, play it with
+ * htw_game_command, read what happened with htw_game_read_events and free it with
+ * htw_game_free. Every function takes NULL for a game, and every buffer must have room for
+ * the size given with it.
+ */
+
+#ifndef HTW_H
+#define HTW_H
+
+/* generated by cbindgen from src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define HTW_ABI_VERSION 1
+
+#define HTW_OK 0
+
+#define HTW_ERROR_NULL -1
+
+#define HTW_ERROR_COMMAND -2
+
+#define HTW_ERROR_DIRECTION -3
+
+#define HTW_ERROR_GAME_OVER -4
+
+#define HTW_DIFFICULTY_EASY 0
+
+#define HTW_DIFFICULTY_NORMAL 1
+
+#define HTW_DIFFICULTY_HARD 2
+
+#define HTW_COMMAND_MOVE 0
+
+#define HTW_COMMAND_SHOOT 1
+
+#define HTW_COMMAND_REST 2
+
+#define HTW_DIRECTION_NORTH 0
+
+#define HTW_DIRECTION_SOUTH 1
+
+#define HTW_DIRECTION_EAST 2
+
+#define HTW_DIRECTION_WEST 3
+
+#define HTW_STATUS_PLAYING 0
+
+#define HTW_STATUS_WON 1
+
+#define HTW_STATUS_LOST 2
+
+#define HTW_EVENT_NO_PASSAGE 0
+
+#define HTW_EVENT_HEAR_BATS 1
+
+#define HTW_EVENT_HEAR_PIT 2
+
+#define HTW_EVENT_SMELL_WUMPUS 3
+
+// the value is the direction.
+#define HTW_EVENT_PASSAGE 4
+
+#define HTW_EVENT_NO_ARROWS 5
+
+#define HTW_EVENT_ARROW_SHOT 6
+
+#define HTW_EVENT_SHOT_SELF 7
+
+#define HTW_EVENT_KILLED_WUMPUS 8
+
+#define HTW_EVENT_SHOT_WALL 9
+
+// the value is the number of arrows.
+#define HTW_EVENT_ARROWS_FOUND 10
+
+#define HTW_EVENT_FELL_IN_PIT 11
+
+#define HTW_EVENT_WALKED_INTO_WUMPUS 12
+
+#define HTW_EVENT_WUMPUS_FOUND_YOU 13
+
+#define HTW_EVENT_CARRIED_BY_BATS 14
+
+#define HTW_EVENT_SEE_WEBS 15
+
+#define HTW_EVENT_CAUGHT_IN_WEB 16
+
+#define HTW_EVENT_STUCK 17
+
+#define HTW_EVENT_SMELL_GAS 18
+
+#define HTW_EVENT_BREATHED_GAS 19
+
+// the value is the hit points recovered.
+#define HTW_EVENT_RESTED 20
+
+#define HTW_EVENT_DIED 21
+
+// a game, only ever used through a pointer.
+typedef struct HtwGame HtwGame;
+
+// how to set up a game. a negative number keeps the value of the difficulty preset.
+typedef struct HtwConfig {
+  // the same seed and config always give the same game.
+  uint64_t seed;
+  uint32_t difficulty;
+  int32_t caverns;
+  int32_t bats;
+  int32_t pits;
+  int32_t arrows;
+  int32_t hit_points;
+} HtwConfig;
+
+// what happened, or what the player senses, in the order the game told it.
+typedef struct HtwEvent {
+  uint32_t kind;
+  // a direction or a number for some events, 0 for the others.
+  uint32_t value;
+} HtwEvent;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// the version of this interface, `HTW_ABI_VERSION` when the header was written.
+uint32_t htw_abi_version(void);
+
+// the normal difficulty with seed 0 and nothing overridden.
+struct HtwConfig htw_config_default(void);
+
+// a new game, or null if it cannot be set up, and then `error` tells why unless it is null.
+// the events of the first cavern are ready to be read.
+struct HtwGame *htw_game_new(const struct HtwConfig *config, char *error, uintptr_t error_size);
+
+void htw_game_free(struct HtwGame *game);
+
+// plays a turn and adds its events to the ones not read yet. a rest ignores the direction.
+// returns `HTW_OK` or one of the `HTW_ERROR_` numbers.
+int32_t htw_game_command(struct HtwGame *game, uint32_t command, uint32_t direction);
+
+// moves up to `capacity` events, oldest first, into `events` and returns how many it moved.
+// the rest wait for the next call.
+uintptr_t htw_game_read_events(struct HtwGame *game, struct HtwEvent *events, uintptr_t capacity);
+
+// how many events are waiting to be read.
+uintptr_t htw_game_pending_events(const struct HtwGame *game);
+
+// adds the events of the player's passages and what they sense, as if they had just got there.
+int32_t htw_game_report_status(struct HtwGame *game);
+
+// the name of an event, e.g. "fell-in-pit", or null for an unknown one. it is never freed.
+const char *htw_event_name(uint32_t kind);
+
+// one of the `HTW_STATUS_` numbers.
+uint32_t htw_game_status(const struct HtwGame *game);
+
+// what killed the player, e.g. "pits", or "" if they are alive. copied like the cavern.
+uintptr_t htw_game_cause(const struct HtwGame *game, char *buffer, uintptr_t size);
+
+// copies the name of the player's cavern into `buffer`, nul-terminated and cut short if
+// `size` is too small, and returns its whole length, like snprintf.
+uintptr_t htw_game_cavern(const struct HtwGame *game, char *buffer, uintptr_t size);
+
+uint32_t htw_game_hit_points(const struct HtwGame *game);
+
+uint32_t htw_game_max_hit_points(const struct HtwGame *game);
+
+uint32_t htw_game_arrows(const struct HtwGame *game);
+
+// the turns played, not counting commands that failed.
+uint32_t htw_game_turns(const struct HtwGame *game);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HTW_H */
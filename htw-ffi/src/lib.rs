@@ -0,0 +1,520 @@
+// a C interface to the game, with include/htw.h written by cbindgen from the `///` comments.
+// a game is an opaque handle made by `htw_game_new` and freed by `htw_game_free`. every
+// function takes null for a game, and every buffer must have room for the size given with it.
+// numbers stay the same from version to version; new ones are only added.
+#![allow(clippy::missing_safety_doc)]
+use htw_core::difficulty::difficulty::Difficulty;
+use htw_core::direction::direction::Direction;
+use htw_core::game_setup::game_setup::GameSetup;
+use htw_core::htw_game::htw_game::GameStatus;
+use htw_core::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+use htw_core::HuntTheWumpus;
+use std::collections::VecDeque;
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+pub const HTW_ABI_VERSION: u32 = 1;
+
+pub const HTW_OK: i32 = 0;
+pub const HTW_ERROR_NULL: i32 = -1;
+pub const HTW_ERROR_COMMAND: i32 = -2;
+pub const HTW_ERROR_DIRECTION: i32 = -3;
+pub const HTW_ERROR_GAME_OVER: i32 = -4;
+
+pub const HTW_DIFFICULTY_EASY: u32 = 0;
+pub const HTW_DIFFICULTY_NORMAL: u32 = 1;
+pub const HTW_DIFFICULTY_HARD: u32 = 2;
+
+pub const HTW_COMMAND_MOVE: u32 = 0;
+pub const HTW_COMMAND_SHOOT: u32 = 1;
+pub const HTW_COMMAND_REST: u32 = 2;
+
+pub const HTW_DIRECTION_NORTH: u32 = 0;
+pub const HTW_DIRECTION_SOUTH: u32 = 1;
+pub const HTW_DIRECTION_EAST: u32 = 2;
+pub const HTW_DIRECTION_WEST: u32 = 3;
+
+pub const HTW_STATUS_PLAYING: u32 = 0;
+pub const HTW_STATUS_WON: u32 = 1;
+pub const HTW_STATUS_LOST: u32 = 2;
+
+pub const HTW_EVENT_NO_PASSAGE: u32 = 0;
+pub const HTW_EVENT_HEAR_BATS: u32 = 1;
+pub const HTW_EVENT_HEAR_PIT: u32 = 2;
+pub const HTW_EVENT_SMELL_WUMPUS: u32 = 3;
+/// the value is the direction.
+pub const HTW_EVENT_PASSAGE: u32 = 4;
+pub const HTW_EVENT_NO_ARROWS: u32 = 5;
+pub const HTW_EVENT_ARROW_SHOT: u32 = 6;
+pub const HTW_EVENT_SHOT_SELF: u32 = 7;
+pub const HTW_EVENT_KILLED_WUMPUS: u32 = 8;
+pub const HTW_EVENT_SHOT_WALL: u32 = 9;
+/// the value is the number of arrows.
+pub const HTW_EVENT_ARROWS_FOUND: u32 = 10;
+pub const HTW_EVENT_FELL_IN_PIT: u32 = 11;
+pub const HTW_EVENT_WALKED_INTO_WUMPUS: u32 = 12;
+pub const HTW_EVENT_WUMPUS_FOUND_YOU: u32 = 13;
+pub const HTW_EVENT_CARRIED_BY_BATS: u32 = 14;
+pub const HTW_EVENT_SEE_WEBS: u32 = 15;
+pub const HTW_EVENT_CAUGHT_IN_WEB: u32 = 16;
+pub const HTW_EVENT_STUCK: u32 = 17;
+pub const HTW_EVENT_SMELL_GAS: u32 = 18;
+pub const HTW_EVENT_BREATHED_GAS: u32 = 19;
+/// the value is the hit points recovered.
+pub const HTW_EVENT_RESTED: u32 = 20;
+pub const HTW_EVENT_DIED: u32 = 21;
+
+// the names of the events, by their numbers, as `HtwMessage::name` gives them.
+const EVENT_NAMES: [&CStr; 22] = [
+    c"no-passage",
+    c"bats",
+    c"pit",
+    c"smell",
+    c"passage",
+    c"no-arrows",
+    c"arrow-shot",
+    c"shot-self",
+    c"killed-wumpus",
+    c"shot-wall",
+    c"arrows-found",
+    c"fell-in-pit",
+    c"walked-into-wumpus",
+    c"wumpus-found-you",
+    c"carried-by-bats",
+    c"webs",
+    c"caught-in-web",
+    c"stuck",
+    c"gas",
+    c"breathed-gas",
+    c"rested",
+    c"died",
+];
+
+/// how to set up a game. a negative number keeps the value of the difficulty preset.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct HtwConfig {
+    /// the same seed and config always give the same game.
+    pub seed: u64,
+    pub difficulty: u32,
+    pub caverns: i32,
+    pub bats: i32,
+    pub pits: i32,
+    pub arrows: i32,
+    pub hit_points: i32,
+}
+
+/// what happened, or what the player senses, in the order the game told it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HtwEvent {
+    pub kind: u32,
+    /// a direction or a number for some events, 0 for the others.
+    pub value: u32,
+}
+
+/// a game, only ever used through a pointer.
+pub struct HtwGame {
+    game: Box<dyn HuntTheWumpus>,
+    log: HtwMessageLog,
+    // the events not read yet.
+    events: VecDeque<HtwEvent>,
+    turns: u32,
+}
+
+impl HtwGame {
+    fn collect_events(&mut self) {
+        let messages = self.log.take_messages();
+        self.events.extend(messages.iter().map(event));
+    }
+}
+
+fn event(message: &HtwMessage) -> HtwEvent {
+    let (kind, value) = match message {
+        HtwMessage::NoPassage => (HTW_EVENT_NO_PASSAGE, 0),
+        HtwMessage::HearBats => (HTW_EVENT_HEAR_BATS, 0),
+        HtwMessage::HearPit => (HTW_EVENT_HEAR_PIT, 0),
+        HtwMessage::SmellWumpus => (HTW_EVENT_SMELL_WUMPUS, 0),
+        HtwMessage::Passage(direction) => (HTW_EVENT_PASSAGE, direction_number(direction)),
+        HtwMessage::NoArrows => (HTW_EVENT_NO_ARROWS, 0),
+        HtwMessage::ArrowShot => (HTW_EVENT_ARROW_SHOT, 0),
+        HtwMessage::PlayerShootsSelfInBack => (HTW_EVENT_SHOT_SELF, 0),
+        HtwMessage::PlayerKillsWumpus => (HTW_EVENT_KILLED_WUMPUS, 0),
+        HtwMessage::PlayerShootsWall => (HTW_EVENT_SHOT_WALL, 0),
+        HtwMessage::ArrowsFound(arrows) => (HTW_EVENT_ARROWS_FOUND, *arrows),
+        HtwMessage::FellInPit => (HTW_EVENT_FELL_IN_PIT, 0),
+        HtwMessage::PlayerMovesToWumpus => (HTW_EVENT_WALKED_INTO_WUMPUS, 0),
+        HtwMessage::WumpusMovesToPlayer => (HTW_EVENT_WUMPUS_FOUND_YOU, 0),
+        HtwMessage::BatsTransport => (HTW_EVENT_CARRIED_BY_BATS, 0),
+        HtwMessage::SeeWebs => (HTW_EVENT_SEE_WEBS, 0),
+        HtwMessage::CaughtInWeb => (HTW_EVENT_CAUGHT_IN_WEB, 0),
+        HtwMessage::PlayerStuck => (HTW_EVENT_STUCK, 0),
+        HtwMessage::SmellGas => (HTW_EVENT_SMELL_GAS, 0),
+        HtwMessage::BreatheGas => (HTW_EVENT_BREATHED_GAS, 0),
+        HtwMessage::PlayerRests(hit_points) => (HTW_EVENT_RESTED, *hit_points),
+        HtwMessage::YouDie => (HTW_EVENT_DIED, 0),
+    };
+    HtwEvent { kind, value }
+}
+
+fn direction_number(direction: &Direction) -> u32 {
+    match direction {
+        Direction::North => HTW_DIRECTION_NORTH,
+        Direction::South => HTW_DIRECTION_SOUTH,
+        Direction::East => HTW_DIRECTION_EAST,
+        Direction::West => HTW_DIRECTION_WEST,
+    }
+}
+
+fn direction(number: u32) -> Option<Direction> {
+    match number {
+        HTW_DIRECTION_NORTH => Some(Direction::North),
+        HTW_DIRECTION_SOUTH => Some(Direction::South),
+        HTW_DIRECTION_EAST => Some(Direction::East),
+        HTW_DIRECTION_WEST => Some(Direction::West),
+        _ => None,
+    }
+}
+
+// copies `text` into `buffer` as a nul-terminated string, cut short if it does not fit, like
+// snprintf. returns the length of the whole text.
+unsafe fn copy_text(text: &str, buffer: *mut c_char, size: usize) -> usize {
+    if !buffer.is_null() && size > 0 {
+        let n = text.len().min(size - 1);
+        ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buffer, n);
+        *buffer.add(n) = 0;
+    }
+    text.len()
+}
+
+fn game_setup(config: &HtwConfig) -> Result<GameSetup, String> {
+    let difficulty = match config.difficulty {
+        HTW_DIFFICULTY_EASY => Difficulty::Easy,
+        HTW_DIFFICULTY_NORMAL => Difficulty::Normal,
+        HTW_DIFFICULTY_HARD => Difficulty::Hard,
+        n => return Err(format!("unknown difficulty {}", n)),
+    };
+    let mut preset = difficulty.preset();
+    let given = |n: i32| u32::try_from(n).ok();
+    if let Some(bats) = given(config.bats) {
+        preset.bats = bats as usize;
+    }
+    if let Some(pits) = given(config.pits) {
+        preset.pits = pits as usize;
+    }
+    if let Some(arrows) = given(config.arrows) {
+        preset.arrows = arrows;
+    }
+    if let Some(hit_points) = given(config.hit_points) {
+        preset.config.max_hit_points = hit_points;
+    }
+    let mut setup = GameSetup::new(preset);
+    setup.caverns = given(config.caverns).map(|n| n as usize);
+    Ok(setup)
+}
+
+/// the version of this interface, `HTW_ABI_VERSION` when the header was written.
+#[no_mangle]
+pub extern "C" fn htw_abi_version() -> u32 {
+    HTW_ABI_VERSION
+}
+
+/// the normal difficulty with seed 0 and nothing overridden.
+#[no_mangle]
+pub extern "C" fn htw_config_default() -> HtwConfig {
+    HtwConfig {
+        seed: 0,
+        difficulty: HTW_DIFFICULTY_NORMAL,
+        caverns: -1,
+        bats: -1,
+        pits: -1,
+        arrows: -1,
+        hit_points: -1,
+    }
+}
+
+/// a new game, or null if it cannot be set up, and then `error` tells why unless it is null.
+/// the events of the first cavern are ready to be read.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_new(
+    config: *const HtwConfig,
+    error: *mut c_char,
+    error_size: usize,
+) -> *mut HtwGame {
+    let Some(config) = config.as_ref() else {
+        copy_text("no config", error, error_size);
+        return ptr::null_mut();
+    };
+    let log = HtwMessageLog::new();
+    let game = game_setup(config).and_then(|setup| {
+        setup
+            .build_from_seed(config.seed, Box::new(log.clone()))
+            .map_err(|e| e.to_string())
+    });
+    match game {
+        Ok(game) => {
+            game.report_status();
+            let mut game = HtwGame {
+                game,
+                log,
+                events: VecDeque::new(),
+                turns: 0,
+            };
+            game.collect_events();
+            Box::into_raw(Box::new(game))
+        }
+        Err(message) => {
+            copy_text(&message, error, error_size);
+            ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_free(game: *mut HtwGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// plays a turn and adds its events to the ones not read yet. a rest ignores the direction.
+/// returns `HTW_OK` or one of the `HTW_ERROR_` numbers.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_command(game: *mut HtwGame, command: u32, direction: u32) -> i32 {
+    let Some(game) = game.as_mut() else {
+        return HTW_ERROR_NULL;
+    };
+    if *game.game.get_status() != GameStatus::Playing {
+        return HTW_ERROR_GAME_OVER;
+    }
+    match (command, self::direction(direction)) {
+        (HTW_COMMAND_MOVE, Some(to)) => game.game.make_move_command(to),
+        (HTW_COMMAND_SHOOT, Some(to)) => game.game.make_shoot_command(to),
+        (HTW_COMMAND_REST, _) => game.game.make_rest_command(),
+        (HTW_COMMAND_MOVE | HTW_COMMAND_SHOOT, None) => return HTW_ERROR_DIRECTION,
+        _ => return HTW_ERROR_COMMAND,
+    }
+    game.game.execute_command();
+    game.turns += 1;
+    game.collect_events();
+    HTW_OK
+}
+
+/// moves up to `capacity` events, oldest first, into `events` and returns how many it moved.
+/// the rest wait for the next call.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_read_events(
+    game: *mut HtwGame,
+    events: *mut HtwEvent,
+    capacity: usize,
+) -> usize {
+    let Some(game) = game.as_mut() else {
+        return 0;
+    };
+    if events.is_null() {
+        return 0;
+    }
+    let n = capacity.min(game.events.len());
+    for (i, event) in game.events.drain(..n).enumerate() {
+        *events.add(i) = event;
+    }
+    n
+}
+
+/// how many events are waiting to be read.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_pending_events(game: *const HtwGame) -> usize {
+    game.as_ref().map_or(0, |game| game.events.len())
+}
+
+/// adds the events of the player's passages and what they sense, as if they had just got there.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_report_status(game: *mut HtwGame) -> i32 {
+    let Some(game) = game.as_mut() else {
+        return HTW_ERROR_NULL;
+    };
+    game.game.report_status();
+    game.collect_events();
+    HTW_OK
+}
+
+/// the name of an event, e.g. "fell-in-pit", or null for an unknown one. it is never freed.
+#[no_mangle]
+pub extern "C" fn htw_event_name(kind: u32) -> *const c_char {
+    EVENT_NAMES
+        .get(kind as usize)
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// one of the `HTW_STATUS_` numbers.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_status(game: *const HtwGame) -> u32 {
+    match game.as_ref().map(|game| game.game.get_status()) {
+        None | Some(GameStatus::Playing) => HTW_STATUS_PLAYING,
+        Some(GameStatus::Won) => HTW_STATUS_WON,
+        Some(GameStatus::Lost(_)) => HTW_STATUS_LOST,
+    }
+}
+
+/// what killed the player, e.g. "pits", or "" if they are alive. copied like the cavern.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_cause(
+    game: *const HtwGame,
+    buffer: *mut c_char,
+    size: usize,
+) -> usize {
+    let cause = match game.as_ref().map(|game| game.game.get_status()) {
+        Some(GameStatus::Lost(cause)) => cause.to_string(),
+        _ => String::new(),
+    };
+    copy_text(&cause, buffer, size)
+}
+
+/// copies the name of the player's cavern into `buffer`, nul-terminated and cut short if
+/// `size` is too small, and returns its whole length, like snprintf.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_cavern(
+    game: *const HtwGame,
+    buffer: *mut c_char,
+    size: usize,
+) -> usize {
+    let cavern = game
+        .as_ref()
+        .map_or("", |game| game.game.get_player_cavern());
+    copy_text(cavern, buffer, size)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_hit_points(game: *const HtwGame) -> u32 {
+    game.as_ref().map_or(0, |game| game.game.get_hit_points())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_max_hit_points(game: *const HtwGame) -> u32 {
+    game.as_ref()
+        .map_or(0, |game| game.game.get_max_hit_points())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_arrows(game: *const HtwGame) -> u32 {
+    game.as_ref().map_or(0, |game| game.game.get_quiver())
+}
+
+/// the turns played, not counting commands that failed.
+#[no_mangle]
+pub unsafe extern "C" fn htw_game_turns(game: *const HtwGame) -> u32 {
+    game.as_ref().map_or(0, |game| game.turns)
+}
+
+#[cfg(test)]
+mod tests_for_htw_ffi {
+    use super::*;
+
+    fn new_game(config: &HtwConfig) -> *mut HtwGame {
+        unsafe { htw_game_new(config, ptr::null_mut(), 0) }
+    }
+
+    fn read_events(game: *mut HtwGame) -> Vec<HtwEvent> {
+        let mut events = vec![];
+        let mut buffer = [HtwEvent::default(); 2];
+        loop {
+            let n = unsafe { htw_game_read_events(game, buffer.as_mut_ptr(), buffer.len()) };
+            if n == 0 {
+                return events;
+            }
+            events.extend_from_slice(&buffer[..n]);
+        }
+    }
+
+    #[test]
+    fn test_play() {
+        let mut config = htw_config_default();
+        config.seed = 5;
+        config.difficulty = HTW_DIFFICULTY_EASY;
+        let game = new_game(&config);
+        assert!(!game.is_null());
+        let events = read_events(game);
+        assert!(events.iter().any(|e| e.kind == HTW_EVENT_PASSAGE));
+        unsafe {
+            assert_eq!(15, htw_game_hit_points(game));
+            assert_eq!(7, htw_game_arrows(game));
+            assert_eq!(HTW_OK, htw_game_command(game, HTW_COMMAND_REST, 0));
+            assert_eq!(1, htw_game_turns(game));
+            assert_eq!(HTW_STATUS_PLAYING, htw_game_status(game));
+            assert_eq!(
+                HTW_ERROR_DIRECTION,
+                htw_game_command(game, HTW_COMMAND_MOVE, 9)
+            );
+            assert_eq!(HTW_ERROR_COMMAND, htw_game_command(game, 9, 0));
+            htw_game_free(game);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let config = htw_config_default();
+        let (a, b) = (new_game(&config), new_game(&config));
+        let mut names = [[0 as c_char; 100]; 2];
+        unsafe {
+            htw_game_cavern(a, names[0].as_mut_ptr(), 100);
+            htw_game_cavern(b, names[1].as_mut_ptr(), 100);
+            htw_game_free(a);
+            htw_game_free(b);
+        }
+        assert_eq!(names[0], names[1]);
+    }
+
+    #[test]
+    fn test_new_game_error() {
+        let mut config = htw_config_default();
+        config.caverns = 3;
+        let mut error = [0 as c_char; 20];
+        let game = unsafe { htw_game_new(&config, error.as_mut_ptr(), error.len()) };
+        assert!(game.is_null());
+        let error = unsafe { CStr::from_ptr(error.as_ptr()) };
+        assert_eq!("3 caverns cannot ho", error.to_str().unwrap());
+    }
+
+    #[test]
+    fn test_new_game_too_many_caverns() {
+        let mut config = htw_config_default();
+        config.caverns = 20000;
+        let mut error = [0 as c_char; 64];
+        let game = unsafe { htw_game_new(&config, error.as_mut_ptr(), error.len()) };
+        assert!(game.is_null());
+        let error = unsafe { CStr::from_ptr(error.as_ptr()) };
+        assert_eq!(
+            "20000 caverns are more than the 1000 a cave can have",
+            error.to_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_null_game() {
+        unsafe {
+            assert_eq!(
+                HTW_ERROR_NULL,
+                htw_game_command(ptr::null_mut(), HTW_COMMAND_REST, 0)
+            );
+            assert_eq!(0, htw_game_pending_events(ptr::null()));
+            htw_game_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_event_names() {
+        let messages = [
+            HtwMessage::NoPassage,
+            HtwMessage::Passage(Direction::East),
+            HtwMessage::ArrowsFound(2),
+            HtwMessage::SeeWebs,
+            HtwMessage::PlayerRests(1),
+            HtwMessage::YouDie,
+        ];
+        for message in &messages {
+            let name = unsafe { CStr::from_ptr(htw_event_name(event(message).kind)) };
+            assert_eq!(message.name(), name.to_str().unwrap());
+        }
+        assert!(htw_event_name(EVENT_NAMES.len() as u32).is_null());
+    }
+}
@@ -0,0 +1,336 @@
+// python bindings, built with `maturin develop` or `maturin build`.
+use htw_core::agent::agent::AgentCommand;
+use htw_core::connections::connections::MapGenerator;
+use htw_core::difficulty::difficulty::Difficulty;
+use htw_core::game_setup::game_setup::GameSetup;
+use htw_core::htw_error::htw_error::HtwError;
+use htw_core::htw_game::htw_game::GameStatus;
+use htw_core::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+use htw_core::map_export::map_export::MapFormat;
+use htw_core::wumpus_env::wumpus_env::{self, Action, EnvConfig, WumpusEnv};
+use htw_core::HuntTheWumpus;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+// the name of a message and the number or the direction it carries, if any.
+type Event = (&'static str, PyObject);
+
+fn error(error: HtwError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+// the options the command line takes. the game checks their limits, e.g. at most
+// `MAX_CAVERNS` caverns, when it is built, and `error` raises them as ValueError.
+#[allow(clippy::too_many_arguments)]
+fn game_setup(
+    difficulty: &str,
+    caverns: Option<usize>,
+    bats: Option<usize>,
+    pits: Option<usize>,
+    arrows: Option<u32>,
+    hit_points: Option<u32>,
+    generator: &str,
+) -> PyResult<GameSetup> {
+    let difficulty = Difficulty::parse(difficulty)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown difficulty \"{}\"", difficulty)))?;
+    let mut preset = difficulty.preset();
+    preset.bats = bats.unwrap_or(preset.bats);
+    preset.pits = pits.unwrap_or(preset.pits);
+    preset.arrows = arrows.unwrap_or(preset.arrows);
+    preset.config.max_hit_points = hit_points.unwrap_or(preset.config.max_hit_points);
+    let mut setup = GameSetup::new(preset);
+    setup.generator = match generator {
+        "random" => MapGenerator::Random,
+        "grid" => MapGenerator::Grid,
+        _ => {
+            let message = format!("unknown generator \"{}\"", generator);
+            return Err(PyValueError::new_err(message));
+        }
+    };
+    setup.caverns = caverns;
+    Ok(setup)
+}
+
+fn events(py: Python, messages: &[HtwMessage]) -> PyResult<Vec<Event>> {
+    messages
+        .iter()
+        .map(|message| {
+            let value = match message {
+                HtwMessage::Passage(direction) => direction.name().into_pyobject(py)?.into_any(),
+                HtwMessage::ArrowsFound(n) | HtwMessage::PlayerRests(n) => {
+                    n.into_pyobject(py)?.into_any()
+                }
+                _ => py.None().into_bound(py),
+            };
+            Ok((message.name(), value.unbind()))
+        })
+        .collect()
+}
+
+fn status_name(status: &GameStatus) -> &'static str {
+    match status {
+        GameStatus::Playing => "playing",
+        GameStatus::Won => "won",
+        GameStatus::Lost(_) => "lost",
+    }
+}
+
+fn cause(status: &GameStatus) -> Option<String> {
+    match status {
+        GameStatus::Lost(cause) => Some(cause.to_string()),
+        _ => None,
+    }
+}
+
+// a game played one command at a time. every command returns what happened as events.
+#[pyclass(unsendable)]
+struct Game {
+    game: Box<dyn HuntTheWumpus>,
+    log: HtwMessageLog,
+    turns: u32,
+}
+
+#[pymethods]
+impl Game {
+    #[new]
+    #[pyo3(signature = (
+        seed=0, difficulty="normal", caverns=None, bats=None, pits=None, arrows=None,
+        hit_points=None, generator="random"
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        seed: u64,
+        difficulty: &str,
+        caverns: Option<usize>,
+        bats: Option<usize>,
+        pits: Option<usize>,
+        arrows: Option<u32>,
+        hit_points: Option<u32>,
+        generator: &str,
+    ) -> PyResult<Game> {
+        let setup = game_setup(
+            difficulty, caverns, bats, pits, arrows, hit_points, generator,
+        )?;
+        let log = HtwMessageLog::new();
+        let game = setup
+            .build_from_seed(seed, Box::new(log.clone()))
+            .map_err(error)?;
+        Ok(Game {
+            game,
+            log,
+            turns: 0,
+        })
+    }
+
+    // "move n", "shoot east" or "rest", as a bot would answer.
+    fn execute(&mut self, py: Python, command: &str) -> PyResult<Vec<Event>> {
+        match AgentCommand::parse(command).map_err(PyValueError::new_err)? {
+            AgentCommand::Move(direction) => self.game.make_move_command(direction),
+            AgentCommand::Shoot(direction) => self.game.make_shoot_command(direction),
+            AgentCommand::Rest | AgentCommand::Quit => self.game.make_rest_command(),
+        }
+        self.play(py)
+    }
+
+    // an action by its number in the reinforcement learning environment.
+    fn step(&mut self, py: Python, action: usize) -> PyResult<Vec<Event>> {
+        match action_from_index(action)? {
+            Action::Move(direction) => self.game.make_move_command(direction),
+            Action::Shoot(direction) => self.game.make_shoot_command(direction),
+            Action::Rest => self.game.make_rest_command(),
+        }
+        self.play(py)
+    }
+
+    // where the player is, what they sense and how they are.
+    fn observe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.log.take_messages();
+        self.game.report_status();
+        let messages = self.log.take_messages();
+        let passages: Vec<&str> = messages
+            .iter()
+            .filter_map(|m| match m {
+                HtwMessage::Passage(direction) => Some(direction.name()),
+                _ => None,
+            })
+            .collect();
+        let percepts: Vec<&str> = messages
+            .iter()
+            .filter(|m| m.is_percept())
+            .map(|m| m.name())
+            .collect();
+        let observation = PyDict::new(py);
+        observation.set_item("cavern", self.game.get_player_cavern())?;
+        observation.set_item("passages", passages)?;
+        observation.set_item("percepts", percepts)?;
+        observation.set_item("hit_points", self.game.get_hit_points())?;
+        observation.set_item("max_hit_points", self.game.get_max_hit_points())?;
+        observation.set_item("arrows", self.game.get_quiver())?;
+        observation.set_item("status", self.status())?;
+        Ok(observation)
+    }
+
+    // the whole cave as "dot" or "svg".
+    #[pyo3(signature = (format="dot"))]
+    fn export_map(&self, format: &str) -> PyResult<String> {
+        let format = match format {
+            "dot" => MapFormat::Dot,
+            "svg" => MapFormat::Svg,
+            _ => {
+                let message = format!("unknown map format \"{}\"", format);
+                return Err(PyValueError::new_err(message));
+            }
+        };
+        Ok(self.game.export_map(format))
+    }
+
+    // "playing", "won" or "lost".
+    #[getter]
+    fn status(&self) -> &'static str {
+        status_name(self.game.get_status())
+    }
+
+    // what killed the player.
+    #[getter]
+    fn cause(&self) -> Option<String> {
+        cause(self.game.get_status())
+    }
+
+    #[getter]
+    fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    #[getter]
+    fn cavern(&self) -> &str {
+        self.game.get_player_cavern()
+    }
+
+    #[getter]
+    fn hit_points(&self) -> u32 {
+        self.game.get_hit_points()
+    }
+
+    #[getter]
+    fn arrows(&self) -> u32 {
+        self.game.get_quiver()
+    }
+}
+
+impl Game {
+    fn play(&mut self, py: Python) -> PyResult<Vec<Event>> {
+        self.log.take_messages();
+        if *self.game.get_status() == GameStatus::Playing {
+            self.game.execute_command();
+            self.turns += 1;
+        }
+        events(py, &self.log.take_messages())
+    }
+}
+
+fn action_from_index(action: usize) -> PyResult<Action> {
+    Action::from_index(action).ok_or_else(|| {
+        let message = format!("actions go from 0 to {}", wumpus_env::N_ACTIONS - 1);
+        PyValueError::new_err(message)
+    })
+}
+
+// the rewards of the environment, with the ones named in `rewards` changed.
+fn env_config(rewards: Option<HashMap<String, f64>>, max_steps: u32) -> PyResult<EnvConfig> {
+    let mut config = EnvConfig {
+        max_steps,
+        ..EnvConfig::default()
+    };
+    let r = &mut config.rewards;
+    for (name, value) in rewards.unwrap_or_default() {
+        let reward = match name.as_str() {
+            "win" => &mut r.win,
+            "death" => &mut r.death,
+            "step" => &mut r.step,
+            "damage" => &mut r.damage,
+            "arrow_shot" => &mut r.arrow_shot,
+            "new_cavern" => &mut r.new_cavern,
+            "wasted_turn" => &mut r.wasted_turn,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown reward \"{}\"",
+                    name
+                )))
+            }
+        };
+        *reward = value;
+    }
+    Ok(config)
+}
+
+// a gym-style environment. observations are lists of `observation_size` numbers and actions
+// are numbers below `n_actions`.
+#[pyclass(unsendable, name = "WumpusEnv")]
+struct PyWumpusEnv {
+    env: WumpusEnv,
+}
+
+#[pymethods]
+impl PyWumpusEnv {
+    #[classattr]
+    const N_ACTIONS: usize = wumpus_env::N_ACTIONS;
+    #[classattr]
+    const OBSERVATION_SIZE: usize = wumpus_env::OBSERVATION_SIZE;
+
+    #[new]
+    #[pyo3(signature = (
+        seed=0, difficulty="normal", caverns=None, bats=None, pits=None, arrows=None,
+        hit_points=None, generator="random", rewards=None, max_steps=1000
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        seed: u64,
+        difficulty: &str,
+        caverns: Option<usize>,
+        bats: Option<usize>,
+        pits: Option<usize>,
+        arrows: Option<u32>,
+        hit_points: Option<u32>,
+        generator: &str,
+        rewards: Option<HashMap<String, f64>>,
+        max_steps: u32,
+    ) -> PyResult<PyWumpusEnv> {
+        let setup = game_setup(
+            difficulty, caverns, bats, pits, arrows, hit_points, generator,
+        )?;
+        let config = env_config(rewards, max_steps)?;
+        let env = WumpusEnv::new(setup, config, seed).map_err(error)?;
+        Ok(PyWumpusEnv { env })
+    }
+
+    fn reset(&mut self, seed: u64) -> PyResult<Vec<f32>> {
+        let observation = self.env.reset(seed).map_err(error)?;
+        Ok(observation.encode().to_vec())
+    }
+
+    // the observation, the reward, whether the episode is done and a dict with the status,
+    // the cause of death, the steps, whether the episode was cut off and the events.
+    fn step<'py>(
+        &mut self,
+        py: Python<'py>,
+        action: usize,
+    ) -> PyResult<(Vec<f32>, f64, bool, Bound<'py, PyDict>)> {
+        let (observation, reward, done, step_info) = self.env.step(action_from_index(action)?);
+        let info = PyDict::new(py);
+        info.set_item("status", status_name(&step_info.status))?;
+        info.set_item("cause", cause(&step_info.status))?;
+        info.set_item("steps", step_info.steps)?;
+        info.set_item("truncated", step_info.truncated)?;
+        info.set_item("events", events(py, &step_info.messages)?)?;
+        Ok((observation.encode().to_vec(), reward, done, info))
+    }
+}
+
+#[pymodule]
+fn hunt_the_wumpus(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Game>()?;
+    module.add_class::<PyWumpusEnv>()?;
+    Ok(())
+}
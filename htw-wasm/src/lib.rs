@@ -0,0 +1,194 @@
+// the game for javascript, e.g. `new Game(42n, "normal")`. it runs in the browser without a
+// server, and built with `wasm-pack build --target web`.
+use htw_core::agent::agent::AgentCommand;
+use htw_core::difficulty::difficulty::Difficulty;
+use htw_core::game_setup::game_setup::GameSetup;
+use htw_core::htw_game::htw_game::GameStatus;
+use htw_core::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+use htw_core::map_export::map_export::MapFormat;
+use htw_core::HuntTheWumpus;
+use wasm_bindgen::prelude::*;
+
+// what happened, or what the player senses, in the order the game told it.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    // e.g. "fell-in-pit" or "passage".
+    pub name: String,
+    // the direction of a passage.
+    pub direction: Option<String>,
+    // the arrows found or the hit points recovered.
+    pub count: Option<u32>,
+}
+
+impl Event {
+    fn new(message: &HtwMessage) -> Event {
+        let mut event = Event {
+            name: String::from(message.name()),
+            direction: None,
+            count: None,
+        };
+        match message {
+            HtwMessage::Passage(direction) => event.direction = Some(direction.name().to_string()),
+            HtwMessage::ArrowsFound(n) | HtwMessage::PlayerRests(n) => event.count = Some(*n),
+            _ => {}
+        }
+        event
+    }
+}
+
+#[wasm_bindgen]
+pub struct Game {
+    game: Box<dyn HuntTheWumpus>,
+    log: HtwMessageLog,
+    turns: u32,
+}
+
+#[wasm_bindgen]
+impl Game {
+    // the game `--seed` would set up at the difficulty, "easy", "normal" or "hard". the events
+    // of the first cavern are ready to be read.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u64, difficulty: &str) -> Result<Game, JsError> {
+        Game::start(seed, difficulty).map_err(|e| JsError::new(&e))
+    }
+
+    // "move n", "shoot east" or "rest", as a bot would answer.
+    pub fn command(&mut self, command: &str) -> Result<(), JsError> {
+        self.play(command).map_err(|e| JsError::new(&e))
+    }
+
+    // the events since they were last read.
+    pub fn events(&mut self) -> Vec<Event> {
+        self.log.take_messages().iter().map(Event::new).collect()
+    }
+
+    // "playing", "won" or "lost".
+    pub fn status(&self) -> String {
+        match self.game.get_status() {
+            GameStatus::Playing => String::from("playing"),
+            GameStatus::Won => String::from("won"),
+            GameStatus::Lost(_) => String::from("lost"),
+        }
+    }
+
+    // what killed the player.
+    pub fn cause(&self) -> Option<String> {
+        match self.game.get_status() {
+            GameStatus::Lost(cause) => Some(cause.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn cavern(&self) -> String {
+        String::from(self.game.get_player_cavern())
+    }
+
+    #[wasm_bindgen(js_name = hitPoints)]
+    pub fn hit_points(&self) -> u32 {
+        self.game.get_hit_points()
+    }
+
+    #[wasm_bindgen(js_name = maxHitPoints)]
+    pub fn max_hit_points(&self) -> u32 {
+        self.game.get_max_hit_points()
+    }
+
+    pub fn arrows(&self) -> u32 {
+        self.game.get_quiver()
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    // the whole cave as svg, to show once the game is over.
+    #[wasm_bindgen(js_name = exportSvg)]
+    pub fn export_svg(&self) -> String {
+        self.game.export_map(MapFormat::Svg)
+    }
+}
+
+impl Game {
+    fn start(seed: u64, difficulty: &str) -> Result<Game, String> {
+        let difficulty = Difficulty::parse(difficulty)
+            .ok_or_else(|| format!("unknown difficulty \"{}\"", difficulty))?;
+        let log = HtwMessageLog::new();
+        let game = GameSetup::new(difficulty.preset())
+            .build_from_seed(seed, Box::new(log.clone()))
+            .map_err(|e| e.to_string())?;
+        game.report_status();
+        Ok(Game {
+            game,
+            log,
+            turns: 0,
+        })
+    }
+
+    fn play(&mut self, command: &str) -> Result<(), String> {
+        if *self.game.get_status() != GameStatus::Playing {
+            return Err(String::from("the game is over"));
+        }
+        match AgentCommand::parse(command)? {
+            AgentCommand::Move(direction) => self.game.make_move_command(direction),
+            AgentCommand::Shoot(direction) => self.game.make_shoot_command(direction),
+            AgentCommand::Rest | AgentCommand::Quit => self.game.make_rest_command(),
+        }
+        self.game.execute_command();
+        self.turns += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_for_htw_wasm {
+    use super::*;
+    // the same tests run in a headless browser or node with `wasm-pack test`.
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_start() {
+        let mut game = Game::start(42, "easy").unwrap();
+        assert_eq!("playing", game.status());
+        assert_eq!(15, game.hit_points());
+        assert!(game.events().iter().any(|e| e.name == "passage"));
+        assert!(game.events().is_empty());
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let a = Game::start(7, "normal").unwrap();
+        let b = Game::start(7, "normal").unwrap();
+        assert_eq!(a.cavern(), b.cavern());
+        assert_eq!(a.export_svg(), b.export_svg());
+    }
+
+    #[test]
+    fn test_play() {
+        let mut game = Game::start(42, "normal").unwrap();
+        game.events();
+        game.play("rest").unwrap();
+        assert_eq!(1, game.turns());
+        assert_eq!(
+            Some(&Event {
+                name: String::from("rested"),
+                direction: None,
+                count: Some(0),
+            }),
+            game.events().first()
+        );
+        assert_eq!(
+            Err(String::from("unknown command \"dance\"")),
+            game.play("dance")
+        );
+    }
+
+    #[test]
+    fn test_unknown_difficulty() {
+        assert_eq!(
+            Some(String::from("unknown difficulty \"nightmare\"")),
+            Game::start(1, "nightmare").err()
+        );
+    }
+}
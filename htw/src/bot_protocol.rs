@@ -0,0 +1,358 @@
+pub mod bot_protocol {
+    use htw_core::agent::agent::{Agent, AgentCommand, Observation, Outcome};
+    use htw_core::direction::direction::Direction;
+    use htw_core::htw_game::htw_game::GameStatus;
+    use htw_core::htw_message::htw_message::HtwMessage;
+    use std::io::{self, BufRead, Read, Write};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+    use std::thread;
+    use std::time::Duration;
+
+    pub const PROTOCOL_VERSION: u32 = 1;
+
+    // plays through another program, e.g. a child process. the bot reads what the player
+    // would see, line by line, and answers each `turn` with `move n`, `shoot e` or `rest`.
+    // reading a reply blocks until the bot writes one, so a bot that may hang should be read
+    // through a `TimedReader`.
+    pub struct BotAgent<R: BufRead, W: Write> {
+        name: String,
+        // the replies of the bot.
+        input: R,
+        // the lines for the bot.
+        output: W,
+        greeted: bool,
+        in_game: bool,
+        error: Option<String>,
+    }
+
+    impl<R: BufRead, W: Write> BotAgent<R, W> {
+        pub fn new(name: &str, input: R, output: W) -> BotAgent<R, W> {
+            BotAgent {
+                name: String::from(name),
+                input,
+                output,
+                greeted: false,
+                in_game: false,
+                error: None,
+            }
+        }
+
+        // why the bot could not go on playing.
+        pub fn error(&self) -> Option<&str> {
+            self.error.as_deref()
+        }
+
+        // tells the bot that there are no more games.
+        pub fn quit(&mut self) -> io::Result<()> {
+            self.send(&[String::from("quit")])
+        }
+
+        fn send(&mut self, lines: &[String]) -> io::Result<()> {
+            for line in lines {
+                writeln!(self.output, "{}", line)?;
+            }
+            self.output.flush()
+        }
+
+        // the next line that is not blank, or `None` once the bot has closed its output.
+        fn read_reply(&mut self) -> io::Result<Option<String>> {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if self.input.read_line(&mut line)? == 0 {
+                    return Ok(None);
+                }
+                if !line.trim().is_empty() {
+                    return Ok(Some(String::from(line.trim())));
+                }
+            }
+        }
+
+        fn ask(&mut self, mut lines: Vec<String>) -> io::Result<Option<AgentCommand>> {
+            loop {
+                self.send(&lines)?;
+                let Some(reply) = self.read_reply()? else {
+                    return Ok(None);
+                };
+                match AgentCommand::parse(&reply) {
+                    Ok(command) => return Ok(Some(command)),
+                    Err(reason) => lines = vec![format!("error {}", reason), String::from("turn")],
+                }
+            }
+        }
+    }
+
+    // reads the output of a bot on a thread of its own, so that a read can give up when the bot
+    // has said nothing for `timeout`.
+    pub struct TimedReader {
+        chunks: Receiver<Vec<u8>>,
+        timeout: Duration,
+        pending: Vec<u8>,
+    }
+
+    impl TimedReader {
+        pub fn new<R: Read + Send + 'static>(mut input: R, timeout: Duration) -> TimedReader {
+            let (sender, chunks) = mpsc::channel();
+            thread::spawn(move || {
+                let mut buffer = [0; 4096];
+                while let Ok(n) = input.read(&mut buffer) {
+                    if n == 0 || sender.send(buffer[..n].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            });
+            TimedReader {
+                chunks,
+                timeout,
+                pending: vec![],
+            }
+        }
+    }
+
+    impl Read for TimedReader {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                match self.chunks.recv_timeout(self.timeout) {
+                    Ok(chunk) => self.pending = chunk,
+                    Err(RecvTimeoutError::Timeout) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("the bot did not reply within {:?}", self.timeout),
+                        ))
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return Ok(0),
+                }
+            }
+            let n = buffer.len().min(self.pending.len());
+            buffer[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    impl<R: BufRead, W: Write> Agent for BotAgent<R, W> {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn act(&mut self, observation: &Observation) -> AgentCommand {
+            if self.error.is_some() {
+                return AgentCommand::Quit;
+            }
+            let mut lines = vec![];
+            if !self.greeted {
+                lines.push(format!("htw {}", PROTOCOL_VERSION));
+                self.greeted = true;
+            }
+            if !self.in_game {
+                lines.push(String::from("game"));
+                self.in_game = true;
+            }
+            lines.extend(observation_lines(observation));
+            match self.ask(lines) {
+                Ok(Some(command)) => command,
+                Ok(None) => {
+                    self.error = Some(String::from("the bot closed its output"));
+                    AgentCommand::Quit
+                }
+                Err(error) => {
+                    self.error = Some(error.to_string());
+                    AgentCommand::Quit
+                }
+            }
+        }
+
+        fn game_over(&mut self, outcome: &Outcome) {
+            if !self.in_game {
+                return;
+            }
+            self.in_game = false;
+            let result = match &outcome.status {
+                GameStatus::Playing => String::from("result unfinished"),
+                GameStatus::Won => String::from("result won"),
+                GameStatus::Lost(cause) => format!("result lost {}", cause.name()),
+            };
+            if let Err(error) = self.send(&[result]) {
+                self.error.get_or_insert(error.to_string());
+            }
+        }
+    }
+
+    // what happened since the last turn, then where the player is, what they sense and how
+    // they are, ending with `turn`.
+    pub fn observation_lines(observation: &Observation) -> Vec<String> {
+        let mut lines: Vec<String> = observation.messages.iter().filter_map(event).collect();
+        lines.push(format!("cavern {}", observation.cavern));
+        let mut passages = vec!["passages"];
+        passages.extend(observation.directions.iter().map(initial));
+        lines.push(passages.join(" "));
+        for message in observation.messages.iter().filter(|m| m.is_percept()) {
+            lines.push(format!("percept {}", message.name()));
+        }
+        lines.push(format!(
+            "status hp={} maxhp={} arrows={}",
+            observation.hit_points, observation.max_hit_points, observation.arrows
+        ));
+        lines.push(String::from("turn"));
+        lines
+    }
+
+    fn initial(direction: &Direction) -> &'static str {
+        match direction {
+            Direction::North => "n",
+            Direction::South => "s",
+            Direction::East => "e",
+            Direction::West => "w",
+        }
+    }
+
+    fn event(message: &HtwMessage) -> Option<String> {
+        match message {
+            HtwMessage::Passage(_) => None,
+            _ if message.is_percept() => None,
+            HtwMessage::ArrowsFound(n) | HtwMessage::PlayerRests(n) => {
+                Some(format!("event {} {}", message.name(), n))
+            }
+            _ => Some(format!("event {}", message.name())),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_bot_protocol {
+        use super::*;
+        use htw_core::agent::agent::run_agent;
+        use htw_core::connection::connection::Connection;
+        use htw_core::connections::connections::Connections;
+        use htw_core::game_builder::game_builder::GameBuilder;
+        use htw_core::htw_game::htw_game::DeathCause;
+        use htw_core::htw_message::htw_message::HtwMessageLog;
+        use htw_core::HuntTheWumpus;
+        use std::collections::BTreeSet;
+
+        // the player in a, the wumpus in b to the east, and one arrow.
+        fn set_up() -> (Box<dyn HuntTheWumpus>, HtwMessageLog) {
+            let caverns = BTreeSet::from([String::from("a"), String::from("b")]);
+            let connections = Connections::new(vec![
+                Connection::new("a", "b", &Direction::East),
+                Connection::new("b", "a", &Direction::West),
+            ]);
+            let log = HtwMessageLog::new();
+            let game = GameBuilder::new(caverns)
+                .connections(connections)
+                .player_cavern("a")
+                .wumpus_cavern("b")
+                .quiver(1)
+                .message_receiver(Box::new(log.clone()))
+                .seed(0)
+                .build()
+                .unwrap();
+            (game, log)
+        }
+
+        fn play(replies: &str) -> (Outcome, Option<String>, String) {
+            let (mut game, log) = set_up();
+            let mut output = vec![];
+            let mut bot = BotAgent::new("bot", replies.as_bytes(), &mut output);
+            let outcome = run_agent(game.as_mut(), &log, &mut bot, 10);
+            let error = bot.error().map(String::from);
+            (outcome, error, String::from_utf8(output).unwrap())
+        }
+
+        #[test]
+        fn test_play_game() {
+            let (outcome, error, output) = play("shoot e\n");
+            assert_eq!(GameStatus::Won, outcome.status);
+            assert_eq!(None, error);
+            assert_eq!(
+                "htw 1\n\
+                 game\n\
+                 cavern a\n\
+                 passages e\n\
+                 percept smell\n\
+                 status hp=10 maxhp=10 arrows=1\n\
+                 turn\n\
+                 result won\n",
+                output
+            );
+        }
+
+        #[test]
+        fn test_invalid_reply_is_asked_again() {
+            let (outcome, _, output) = play("dance\n\nshoot e\n");
+            assert_eq!(GameStatus::Won, outcome.status);
+            assert!(output.contains("turn\nerror unknown command \"dance\"\nturn\nresult won\n"));
+        }
+
+        #[test]
+        fn test_lost_names_cause() {
+            // the arrows hit the wall, and the wumpus comes.
+            let (outcome, _, output) = play("shoot w\nshoot w\n");
+            assert_eq!(GameStatus::Lost(DeathCause::FoundByWumpus), outcome.status);
+            assert!(output.ends_with("result lost wumpus-found-you\n"));
+        }
+
+        #[test]
+        fn test_bot_closes_output() {
+            let (outcome, error, output) = play("");
+            assert_eq!(GameStatus::Playing, outcome.status);
+            assert_eq!(0, outcome.turns);
+            assert_eq!(Some(String::from("the bot closed its output")), error);
+            assert!(output.ends_with("turn\nresult unfinished\n"));
+        }
+
+        // a bot that never answers.
+        struct Silent;
+
+        impl Read for Silent {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                thread::sleep(Duration::from_secs(1));
+                Ok(0)
+            }
+        }
+
+        #[test]
+        fn test_bot_times_out() {
+            let (mut game, log) = set_up();
+            let input = io::BufReader::new(TimedReader::new(Silent, Duration::from_millis(10)));
+            let mut bot = BotAgent::new("bot", input, io::sink());
+            let outcome = run_agent(game.as_mut(), &log, &mut bot, 10);
+            assert_eq!(0, outcome.turns);
+            assert_eq!(Some("the bot did not reply within 10ms"), bot.error());
+        }
+
+        #[test]
+        fn test_timed_reader_passes_replies_on() {
+            let mut input = io::BufReader::new(TimedReader::new(
+                "rest\n".as_bytes(),
+                Duration::from_secs(1),
+            ));
+            let mut line = String::new();
+            input.read_line(&mut line).unwrap();
+            assert_eq!("rest\n", line);
+        }
+
+        #[test]
+        fn test_observation_lines_report_events() {
+            let (mut game, log) = set_up();
+            game.report_status();
+            log.take_messages();
+            game.make_move_command(Direction::West);
+            game.execute_command();
+            let messages = log.take_messages();
+            let observation = Observation {
+                cavern: game.get_player_cavern(),
+                messages: &messages,
+                directions: vec![Direction::East],
+                hit_points: 10,
+                max_hit_points: 10,
+                arrows: 1,
+                knowledge_map: game.knowledge_map(),
+                assessments: &[],
+            };
+            assert_eq!(
+                Some(&String::from("event no-passage")),
+                observation_lines(&observation).first()
+            );
+        }
+    }
+}
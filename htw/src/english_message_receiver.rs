@@ -1,82 +1,83 @@
 pub mod english_htw_message_receiver {
-    use crate::{Direction, HtwMessageReceiver};
-    use std::process;
-
-    pub struct EnglishHtwMessageReceiver {}
-
-    impl EnglishHtwMessageReceiver {}
-
-    impl HtwMessageReceiver for EnglishHtwMessageReceiver {
-        fn no_passage(&self) {
-            println!("No Passage.");
-        }
-
-        fn hear_bats(&self) {
-            println!("You hear chirping.");
-        }
-
-        fn hear_pit(&self) {
-            println!("You hear wind.");
-        }
-
-        fn smell_wumpus(&self) {
-            println!("There is a terrible smell.");
-        }
-
-        fn passage(&self, direction: &Direction) {
-            println!("You can go {}", direction.name());
-        }
-
-        fn no_arrows(&self) {
-            println!("You have no arrows.");
-        }
-
-        fn arrow_shot(&self) {
-            println!("Thwang!");
-        }
-
-        fn player_shoots_self_in_back(&self) {
-            println!("Ow!  You shot yourself in the back.");
-        }
-
-        fn player_kills_wumpus(&self) {
-            println!("You killed the Wumpus.");
-            process::exit(0);
-        }
-
-        fn player_shoots_wall(&self) {
-            println!("You shot the wall and the ricochet hurt you.");
-        }
-
-        fn arrows_found(&self, arrows_found: u32) {
-            let mut plural = "";
-            if arrows_found != 1 {
-                plural = "s";
+    use htw_core::htw_message::htw_message::{HtwMessage, HtwMessageSink};
+
+    pub fn english_text(message: &HtwMessage) -> String {
+        match message {
+            HtwMessage::NoPassage => String::from("No Passage."),
+            HtwMessage::HearBats => String::from("You hear chirping."),
+            HtwMessage::HearPit => String::from("You hear wind."),
+            HtwMessage::SmellWumpus => String::from("There is a terrible smell."),
+            HtwMessage::Passage(direction) => format!("You can go {}", direction.name()),
+            HtwMessage::NoArrows => String::from("You have no arrows."),
+            HtwMessage::ArrowShot => String::from("Thwang!"),
+            HtwMessage::PlayerShootsSelfInBack => {
+                String::from("Ow!  You shot yourself in the back.")
             }
-            println!("You found {} arrow{}.", arrows_found, plural);
-        }
-
-        fn fell_in_pit(&self) {
-            println!("You fell in a pit and hurt yourself.");
+            HtwMessage::PlayerKillsWumpus => String::from("You killed the Wumpus."),
+            HtwMessage::PlayerShootsWall => {
+                String::from("You shot the wall and the ricochet hurt you.")
+            }
+            HtwMessage::ArrowsFound(arrows_found) => {
+                format!("You found {} arrow{}.", arrows_found, plural(*arrows_found))
+            }
+            HtwMessage::FellInPit => String::from("You fell in a pit and hurt yourself."),
+            HtwMessage::PlayerMovesToWumpus => {
+                String::from("You walked into the waiting arms of the Wumpus.")
+            }
+            HtwMessage::WumpusMovesToPlayer => String::from("The Wumpus has found you."),
+            HtwMessage::BatsTransport => String::from("Some bats carried you away."),
+            HtwMessage::SeeWebs => String::from("You see silk threads."),
+            HtwMessage::CaughtInWeb => String::from("You are caught in a spider web."),
+            HtwMessage::PlayerStuck => String::from("You struggle free of the web."),
+            HtwMessage::SmellGas => String::from("There is an acrid smell."),
+            HtwMessage::BreatheGas => String::from("You breathe poison gas and choke."),
+            HtwMessage::PlayerRests(0) => String::from("You rest."),
+            HtwMessage::PlayerRests(hit_points_recovered) => format!(
+                "You rest and recover {} hit point{}.",
+                hit_points_recovered,
+                plural(*hit_points_recovered)
+            ),
+            HtwMessage::YouDie => String::from("You have died of your wounds."),
         }
+    }
 
-        fn player_moves_to_wumpus(&self) {
-            println!("You walked into the waiting arms of the Wumpus.");
-            process::exit(0);
+    fn plural(count: u32) -> &'static str {
+        match count {
+            1 => "",
+            _ => "s",
         }
+    }
 
-        fn wumpus_moves_to_player(&self) {
-            println!("The Wumpus has found you.");
-            process::exit(0);
-        }
+    // prints every message.
+    pub struct EnglishHtwMessageReceiver {}
 
-        fn bats_transport(&self) {
-            println!("Some bats carried you away.");
+    impl HtwMessageSink for EnglishHtwMessageReceiver {
+        fn say(&self, message: HtwMessage) {
+            println!("{}", english_text(&message));
         }
+    }
 
-        fn you_die(&self) {
-            println!("You have died of your wounds.");
-            process::exit(0);
+    #[cfg(test)]
+    mod tests_for_english_htw_message_receiver {
+        use super::*;
+        use htw_core::direction::direction::Direction;
+
+        #[test]
+        fn test_english_text() {
+            assert_eq!("You hear wind.", english_text(&HtwMessage::HearPit));
+            assert_eq!(
+                "You can go North",
+                english_text(&HtwMessage::Passage(Direction::North))
+            );
+            assert_eq!(
+                "You found 1 arrow.",
+                english_text(&HtwMessage::ArrowsFound(1))
+            );
+            assert_eq!("You rest.", english_text(&HtwMessage::PlayerRests(0)));
+            assert_eq!(
+                "You rest and recover 2 hit points.",
+                english_text(&HtwMessage::PlayerRests(2))
+            );
         }
     }
 }
@@ -1,132 +1,881 @@
-use htw::connections::connections;
-use htw::direction::direction::Direction;
+use clap::{Parser, ValueEnum};
+use htw::bot_protocol::bot_protocol::{BotAgent, TimedReader};
 use htw::english_message_receiver::english_htw_message_receiver::EnglishHtwMessageReceiver;
-use htw::htw_game::htw_game::Caverns;
-use htw::htw_game::htw_game::HuntTheWumpusGame;
-use htw::HuntTheWumpus;
-use rand::Rng;
-use std::collections::HashSet;
-use std::io;
-use std::process;
+use htw::parser::parser::{CommandParser, MapView, ParseError, ParseErrorReason, ParsedCommand};
+use htw::simulation::simulation::{self, GameRecord, Statistics};
+use htw_core::agent::agent::{Agent, LogicalAgent, RandomAgent};
+use htw_core::connections::connections::MapGenerator;
+use htw_core::difficulty::difficulty::Difficulty;
+use htw_core::game_setup::game_setup::{GameSetup, MAX_CAVERNS};
+use htw_core::htw_game::htw_game::GameStatus;
+use htw_core::htw_message::htw_message::HtwMessageLog;
+use htw_core::inference::inference::Danger;
+use htw_core::map_export::map_export::MapFormat;
+use htw_core::map_file::map_file;
+use htw_core::HtwMessageReceiver;
+use htw_core::HuntTheWumpus;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Config, Context, Editor, Helper};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufReader, IsTerminal};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::time::Duration;
+
+/// Hunt the Wumpus
+#[derive(Parser)]
+#[command(version)]
+struct Options {
+  /// Seed for the random number generator, to replay the same cave and game
+  #[arg(long)]
+  seed: Option<u64>,
+  /// Number of caverns [default: picked at random, depending on the difficulty]
+  #[arg(long, value_parser = clap::value_parser!(u32).range(2..=MAX_CAVERNS as i64))]
+  caverns: Option<u32>,
+  /// Number of caverns with bats [default: depends on the difficulty]
+  #[arg(long)]
+  bats: Option<u32>,
+  /// Number of caverns with pits [default: depends on the difficulty]
+  #[arg(long)]
+  pits: Option<u32>,
+  /// Arrows in the quiver at the start [default: depends on the difficulty]
+  #[arg(long)]
+  arrows: Option<u32>,
+  /// Hit points at the start, which is also the maximum [default: depends on the difficulty]
+  #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+  hit_points: Option<u32>,
+  /// Preset for the cave size, the hazards, the arrows and the hit points
+  #[arg(long, value_enum, default_value_t = DifficultyOption::Normal)]
+  difficulty: DifficultyOption,
+  /// Language of the game messages
+  #[arg(long, value_enum, default_value_t = Language::En)]
+  language: Language,
+  /// Load the caverns and passages from a file of `from | direction | to` lines
+  #[arg(long, conflicts_with_all = ["caverns", "generator"])]
+  map_file: Option<PathBuf>,
+  /// Config file holding the command macros [default: ~/.htwrc]
+  #[arg(long)]
+  config: Option<PathBuf>,
+  /// Play full screen, with panes for the map, the status and the messages
+  #[arg(long)]
+  tui: bool,
+  /// How the passages between the caverns are laid out
+  #[arg(long, value_enum, default_value_t = Generator::Random)]
+  generator: Generator,
+  /// Write the whole cave to FILE and quit: SVG for a `.svg` file, Graphviz DOT otherwise, `-` for the standard output
+  #[arg(long, value_name = "FILE")]
+  dump_map: Option<PathBuf>,
+  /// Let an agent play GAMES games, with the seeds counting up from --seed, print statistics and quit
+  #[arg(long, value_name = "GAMES", conflicts_with_all = ["map_file", "tui", "dump_map"])]
+  simulate: Option<u64>,
+  /// The agent playing the simulated games
+  #[arg(long, value_enum, default_value_t = AgentOption::Logical, requires = "simulate")]
+  agent: AgentOption,
+  /// Turns before a simulated game is given up as unfinished
+  #[arg(long, default_value_t = 1000, requires = "simulate")]
+  max_turns: u32,
+  /// Let the program COMMAND, with any ARGS, play the simulated games, one after another, through the bot protocol. It takes the rest of the command line, so it comes last
+  #[arg(
+    long,
+    value_name = "COMMAND ARGS",
+    num_args = 1..,
+    allow_hyphen_values = true,
+    requires = "simulate",
+    conflicts_with = "agent"
+  )]
+  bot: Option<Vec<String>>,
+  /// Seconds the bot may take over a reply before it is stopped
+  #[arg(long, value_name = "SECONDS", default_value_t = 10, requires = "bot")]
+  bot_timeout: u64,
+  /// Write each simulated game to FILE: JSON with the statistics for a `.json` file, CSV otherwise
+  #[arg(long, value_name = "FILE", requires = "simulate")]
+  report: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DifficultyOption {
+  Easy,
+  Normal,
+  Hard,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Language {
+  /// English
+  En,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AgentOption {
+  /// Wanders at random
+  Random,
+  /// Works out where the hazards are and waits for the wumpus where few passages meet
+  Logical,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Generator {
+  /// Each cavern links to random others
+  Random,
+  /// The caverns lie on a square grid, linked to their neighbours
+  Grid,
+}
+
+#[allow(clippy::module_inception)]
+mod tui;
 
 fn main() {
-  let message_receiver = EnglishHtwMessageReceiver {};
-  let caverns = create_caverns();
-  let mut game = HuntTheWumpusGame::new(Box::new(message_receiver), caverns);
-  game.connect_caverns();
-  set_special_caverns(&mut game);
-  game.set_quiver(5);
-  game.make_rest_command();
-  game.execute_command();
+  let options = Options::parse();
+  if let Some(path) = &options.dump_map {
+    dump_map(&options, path);
+  }
+  if let Some(games) = options.simulate {
+    simulate(&options, games);
+  }
+  if options.tui {
+    run_tui(&options);
+  }
+  let message_receiver: Box<dyn HtwMessageReceiver> = match options.language {
+    Language::En => Box::new(EnglishHtwMessageReceiver {}),
+  };
+  let mut game = match build_game(&options, message_receiver) {
+    Ok(game) => game,
+    Err(error) => {
+      eprintln!("error: {}", error);
+      process::exit(1);
+    }
+  };
+  let config_path = options
+    .config
+    .clone()
+    .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".htwrc")));
+  let mut parser = CommandParser::new();
+  if let Some(path) = &config_path {
+    load_config(path, &mut parser);
+  }
+  let history_path = env::var_os("HOME").map(|home| PathBuf::from(home).join(".htw_history"));
+  let mut input = Input::new(history_path);
+  game.report_status();
   loop {
-    println!("{}", game.get_player_cavern());
-    println!(
-      "Health: {} arrows: {}",
-      game.get_hit_points(),
-      game.get_quiver()
-    );
-    game.make_rest_command();
-    println!(">");
-    let mut command = String::new();
-    match io::stdin().read_line(&mut command) {
-      Ok(_) => match &*command.trim().to_lowercase() {
-        "e" => game.make_move_command(Direction::East),
-        "w" => game.make_move_command(Direction::West),
-        "n" => game.make_move_command(Direction::North),
-        "s" => game.make_move_command(Direction::South),
-        "se" => game.make_shoot_command(Direction::East),
-        "sw" => game.make_shoot_command(Direction::West),
-        "sn" => game.make_shoot_command(Direction::North),
-        "ss" => game.make_shoot_command(Direction::South),
-        "q" => process::exit(0),
-        _ => game.make_rest_command(),
+    print_location(game.as_ref());
+    input.set_words(parser.words(), game.knowledge_map().visited().to_vec());
+    let line = match input.read_line() {
+      Ok(Some(line)) => line,
+      Ok(None) => process::exit(0),
+      // the input will not get better by asking again.
+      Err(error) => {
+        eprintln!("error: cannot read the input: {}", error);
+        process::exit(1);
+      }
+    };
+    let commands = match parser.parse_line(&line) {
+      Ok(commands) => commands,
+      Err(error) => {
+        print_parse_error(&error);
+        continue;
+      }
+    };
+    let n_commands = commands.len();
+    for (i, command) in commands.into_iter().enumerate() {
+      let outcome = run_command(command, game.as_mut(), &mut parser, &config_path);
+      if game.get_status() != &GameStatus::Playing {
+        process::exit(0);
+      }
+      if i + 1 < n_commands {
+        if outcome == Outcome::Interrupted {
+          println!("The rest of your commands are cancelled.");
+          break;
+        }
+        print_location(game.as_ref());
+      }
+    }
+  }
+}
+
+fn run_tui(options: &Options) -> ! {
+  if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+    eprintln!("error: --tui needs a terminal");
+    process::exit(1);
+  }
+  let log = HtwMessageLog::new();
+  let game = match build_game(options, Box::new(log.clone())) {
+    Ok(game) => game,
+    Err(error) => {
+      eprintln!("error: {}", error);
+      process::exit(1);
+    }
+  };
+  if let Err(error) = tui::tui::run(game, log) {
+    eprintln!("error: {}", error);
+    process::exit(1);
+  }
+  process::exit(0);
+}
+
+fn dump_map(options: &Options, path: &Path) -> ! {
+  let result = build_game(options, Box::new(EnglishHtwMessageReceiver {})).and_then(|game| {
+    if path == Path::new("-") {
+      print!("{}", game.export_map(MapFormat::Dot));
+      return Ok(());
+    }
+    let format = match path.extension() {
+      Some(extension) if extension.eq_ignore_ascii_case("svg") => MapFormat::Svg,
+      _ => MapFormat::Dot,
+    };
+    fs::write(path, game.export_map(format))
+      .map_err(|error| format!("cannot write {}: {}", path.display(), error).into())
+  });
+  if let Err(error) = result {
+    eprintln!("error: {}", error);
+    process::exit(1);
+  }
+  process::exit(0);
+}
+
+fn simulate(options: &Options, games: u64) -> ! {
+  let setup = game_setup(options);
+  let first_seed = options.seed.unwrap_or(0);
+  let agent = options.agent;
+  let make_agent = move |seed| -> Box<dyn Agent> {
+    match agent {
+      AgentOption::Random => Box::new(RandomAgent::new(seed)),
+      AgentOption::Logical => Box::new(LogicalAgent::new()),
+    }
+  };
+  let seeds = first_seed..first_seed.saturating_add(games);
+  let (records, player) = match &options.bot {
+    Some(command) => match play_bot(options, &setup, command, seeds) {
+      Ok(records) => (records, command.join(" ")),
+      Err(error) => {
+        eprintln!("error: {}", error);
+        process::exit(1);
+      }
+    },
+    None => (
+      simulation::simulate(&setup, seeds, options.max_turns, &make_agent),
+      format!("the {} agent", make_agent(0).name()),
+    ),
+  };
+  let statistics = Statistics::new(&records);
+  print_statistics(&statistics, &player);
+  if let Some(path) = &options.report {
+    let report = match path.extension() {
+      Some(extension) if extension.eq_ignore_ascii_case("json") => {
+        simulation::to_json(&statistics, &records).map_err(|error| error.to_string())
+      }
+      _ => Ok(simulation::to_csv(&records)),
+    };
+    let result = report.and_then(|report| {
+      fs::write(path, report).map_err(|error| format!("cannot write {}: {}", path.display(), error))
+    });
+    if let Err(error) = result {
+      eprintln!("error: {}", error);
+      process::exit(1);
+    }
+  }
+  process::exit(0);
+}
+
+// plays the games against a single bot process, which hears of each game as it starts and ends.
+fn play_bot(
+  options: &Options,
+  setup: &GameSetup,
+  command: &[String],
+  seeds: Range<u64>,
+) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+  let (program, args) = command.split_first().ok_or("the bot command is empty")?;
+  let name = command.join(" ");
+  let mut child = process::Command::new(program)
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .map_err(|error| format!("cannot start {}: {}", program, error))?;
+  let input = child.stdout.take().ok_or("the bot has no output")?;
+  let input = BufReader::new(TimedReader::new(
+    input,
+    Duration::from_secs(options.bot_timeout),
+  ));
+  let output = child.stdin.take().ok_or("the bot has no input")?;
+  let mut bot = BotAgent::new(&name, input, output);
+  let mut records = vec![];
+  for seed in seeds {
+    records.push(simulation::play(setup, seed, &mut bot, options.max_turns));
+    if let Some(error) = bot.error() {
+      let error = format!("bot {} in game {}: {}", name, seed, error);
+      let _ = child.kill();
+      return Err(error.into());
+    }
+  }
+  // the bot may have gone already, which does not spoil the games.
+  let _ = bot.quit();
+  drop(bot);
+  child.wait()?;
+  Ok(records)
+}
+
+fn print_statistics(statistics: &Statistics, player: &str) {
+  println!("{} games played by {}", statistics.games, player);
+  println!(
+    "won {} ({:.1}%), lost {}, unfinished {}, failed {}",
+    statistics.won,
+    statistics.win_rate * 100.0,
+    statistics.lost,
+    statistics.unfinished,
+    statistics.failed
+  );
+  for (label, causes) in [
+    ("deaths", &statistics.deaths),
+    ("failures", &statistics.failures),
+  ] {
+    let mut causes: Vec<(&String, &usize)> = causes.iter().collect();
+    causes.sort_by(|a, b| b.1.cmp(a.1));
+    let causes: Vec<String> = causes
+      .iter()
+      .map(|(cause, n)| format!("{} ({})", cause, n))
+      .collect();
+    if !causes.is_empty() {
+      println!("{}: {}", label, causes.join(", "));
+    }
+  }
+  println!(
+    "average turns {:.1}, arrows shot {:.1}",
+    statistics.average_turns, statistics.average_arrows_shot
+  );
+}
+
+// reads commands with line editing, history and completion when a person is at the terminal,
+// and line by line when the input is scripted.
+enum Input {
+  Terminal(Box<Editor<CommandHelper, FileHistory>>, Option<PathBuf>),
+  Plain,
+}
+
+impl Input {
+  fn new(history_path: Option<PathBuf>) -> Input {
+    if !io::stdin().is_terminal() {
+      return Input::Plain;
+    }
+    let config = Config::builder()
+      .completion_type(CompletionType::List)
+      .build();
+    let Ok(mut editor) = Editor::with_config(config) else {
+      return Input::Plain;
+    };
+    editor.set_helper(Some(CommandHelper {
+      words: vec![],
+      caverns: vec![],
+    }));
+    if let Some(path) = &history_path {
+      // there is no history yet on the first run.
+      let _ = editor.load_history(path);
+    }
+    Input::Terminal(Box::new(editor), history_path)
+  }
+
+  fn set_words(&mut self, words: Vec<String>, caverns: Vec<String>) {
+    if let Input::Terminal(editor, _) = self {
+      if let Some(helper) = editor.helper_mut() {
+        helper.words = words;
+        helper.caverns = caverns;
+      }
+    }
+  }
+
+  // returns None at the end of the input.
+  fn read_line(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+    match self {
+      Input::Terminal(editor, history_path) => match editor.readline("> ") {
+        Ok(line) => {
+          // the line was read, so a history that cannot be kept does not lose it.
+          if !line.trim().is_empty() && editor.add_history_entry(line.as_str()).is_ok() {
+            if let Some(path) = history_path {
+              let _ = editor.save_history(path);
+            }
+          }
+          Ok(Some(line))
+        }
+        Err(ReadlineError::Eof) | Err(ReadlineError::Interrupted) => Ok(None),
+        Err(error) => Err(error.into()),
       },
-      Err(error) => println!("error: {}", error),
+      Input::Plain => {
+        println!(">");
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line)? {
+          0 => Ok(None),
+          _ => Ok(Some(line)),
+        }
+      }
+    }
+  }
+}
+
+struct CommandHelper {
+  words: Vec<String>,
+  // the caverns `goto` can take the player to.
+  caverns: Vec<String>,
+}
+
+impl Completer for CommandHelper {
+  type Candidate = String;
+
+  // completes the word under the cursor, or the whole cavern name after `goto`.
+  fn complete(
+    &self,
+    line: &str,
+    pos: usize,
+    _context: &Context<'_>,
+  ) -> rustyline::Result<(usize, Vec<String>)> {
+    let step_start = line[..pos].rfind(';').map_or(0, |i| i + 1);
+    let step = line[step_start..pos].trim_start();
+    if step.len() >= 5 && step[..5].eq_ignore_ascii_case("goto ") {
+      let start = pos - step.len() + 5;
+      let prefix = line[start..pos].to_lowercase();
+      let candidates = self
+        .caverns
+        .iter()
+        .filter(|cavern| cavern.to_lowercase().starts_with(&prefix))
+        .cloned()
+        .collect();
+      return Ok((start, candidates));
+    }
+    let start = line[..pos]
+      .rfind(|c: char| c.is_whitespace() || c == ';')
+      .map_or(0, |i| i + 1);
+    let prefix = line[start..pos].to_lowercase();
+    let candidates = self
+      .words
+      .iter()
+      .filter(|word| word.starts_with(&prefix))
+      .cloned()
+      .collect();
+    Ok((start, candidates))
+  }
+}
+
+impl Hinter for CommandHelper {
+  type Hint = String;
+}
+
+impl Highlighter for CommandHelper {}
+
+impl Validator for CommandHelper {}
+
+impl Helper for CommandHelper {}
+
+// what a command did to the game.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+  NoTurn,
+  Turn,
+  // something happened that the commands after it did not expect.
+  Interrupted,
+}
+
+fn run_command(
+  command: ParsedCommand,
+  game: &mut dyn HuntTheWumpus,
+  parser: &mut CommandParser,
+  config_path: &Option<PathBuf>,
+) -> Outcome {
+  match command {
+    ParsedCommand::Move(direction) => game.make_move_command(direction),
+    ParsedCommand::Shoot(direction) => game.make_shoot_command(direction),
+    ParsedCommand::Rest => game.make_rest_command(),
+    ParsedCommand::Help => {
+      print_help();
+      return Outcome::NoTurn;
+    }
+    ParsedCommand::Status => {
+      game.report_status();
+      return Outcome::NoTurn;
+    }
+    ParsedCommand::Map(view) => {
+      print_map(game, view);
+      return Outcome::NoTurn;
     }
+    ParsedCommand::Hint => {
+      print_hints(game);
+      return Outcome::NoTurn;
+    }
+    ParsedCommand::Goto(cavern) => return run_goto(&cavern, game),
+    ParsedCommand::Quit => process::exit(0),
+    ParsedCommand::Define(name, commands) => {
+      parser.define_macro(&name, &commands);
+      println!("Defined '{}' as '{}'.", name, commands);
+      if let Some(path) = config_path {
+        if let Err(error) = save_macro(path, &name, &commands) {
+          println!("error: cannot save to {}: {}", path.display(), error);
+        }
+      }
+      return Outcome::NoTurn;
+    }
+  }
+  game.execute_command();
+  match game.was_interrupted() {
+    true => Outcome::Interrupted,
+    false => Outcome::Turn,
+  }
+}
+
+fn print_location(game: &dyn HuntTheWumpus) {
+  println!("{}", game.get_player_cavern());
+  println!(
+    "Health: {} arrows: {}",
+    game.get_hit_points(),
+    game.get_quiver()
+  );
+}
+
+fn print_map(game: &dyn HuntTheWumpus, view: MapView) {
+  let knowledge_map = game.knowledge_map();
+  match view {
+    MapView::Drawing => {
+      let rows = knowledge_map.draw(game.get_player_cavern(), 61, 21);
+      let rows: Vec<&str> = rows.iter().map(|row| row.trim_end()).collect();
+      let first = rows.iter().position(|row| !row.is_empty()).unwrap_or(0);
+      let last = rows.iter().rposition(|row| !row.is_empty()).unwrap_or(0);
+      for row in &rows[first..=last] {
+        println!("{}", row);
+      }
+      println!("@ you  o visited  ! danger nearby  ? unexplored passage");
+    }
+    MapView::List => {
+      println!("Caverns you have been to:");
+      for (number, cavern) in knowledge_map.visited().iter().enumerate() {
+        let here = if cavern == game.get_player_cavern() {
+          " (you are here)"
+        } else {
+          ""
+        };
+        println!("  {}. {}{}", number + 1, cavern, here);
+        let Some(knowledge) = knowledge_map.cavern(cavern) else {
+          continue;
+        };
+        for direction in &knowledge.passages {
+          let destination = knowledge_map
+            .destination(cavern, *direction)
+            .unwrap_or("not explored");
+          println!("    {}: {}", direction.name().to_lowercase(), destination);
+        }
+        if !knowledge.percepts.is_empty() {
+          println!("    nearby: {}", knowledge.percepts.join(", "));
+        }
+      }
+    }
+  }
+}
+
+// walks to the cavern one turn at a time, and stops when something happens on the way.
+fn run_goto(target: &str, game: &mut dyn HuntTheWumpus) -> Outcome {
+  let Some(cavern) = find_cavern(game, target) else {
+    println!("You have not been to '{}'.", target);
+    return Outcome::NoTurn;
+  };
+  if let Some(hazard) = game
+    .knowledge_map()
+    .cavern(&cavern)
+    .and_then(|k| k.found.first())
+  {
+    println!(
+      "You ran into {} in {}, so you will not walk there by goto.",
+      hazard, cavern
+    );
+    return Outcome::NoTurn;
+  }
+  let Some(route) = game.route_to(&cavern) else {
+    println!("You know no safe way there.");
+    return Outcome::NoTurn;
+  };
+  if route.is_empty() {
+    println!("You are already there.");
+    return Outcome::NoTurn;
+  }
+  let n_steps = route.len();
+  for (i, (direction, next)) in route.into_iter().enumerate() {
+    let known_percepts = game
+      .knowledge_map()
+      .cavern(&next)
+      .map(|knowledge| knowledge.percepts.clone())
+      .unwrap_or_default();
+    game.make_move_command(direction);
     game.execute_command();
+    if game.was_interrupted() || game.get_player_cavern() != next {
+      return Outcome::Interrupted;
+    }
+    let percepts = &game.knowledge_map().cavern(&next).unwrap().percepts;
+    if percepts.iter().any(|p| !known_percepts.contains(p)) {
+      println!("You sense something new and stop.");
+      return Outcome::Interrupted;
+    }
+    if i + 1 < n_steps {
+      print_location(game);
+    }
+  }
+  Outcome::Turn
+}
+
+// finds a visited cavern by its number on the map list, its name or the start of its name.
+fn find_cavern(game: &dyn HuntTheWumpus, target: &str) -> Option<String> {
+  let visited = game.knowledge_map().visited();
+  if let Ok(number) = target.parse::<usize>() {
+    return visited.get(number.checked_sub(1)?).cloned();
+  }
+  let target = target.to_lowercase();
+  if let Some(cavern) = visited.iter().find(|c| c.to_lowercase() == target) {
+    return Some(cavern.clone());
+  }
+  match visited
+    .iter()
+    .filter(|c| c.to_lowercase().starts_with(&target))
+    .collect::<Vec<&String>>()[..]
+  {
+    [cavern] => Some(cavern.clone()),
+    _ => None,
+  }
+}
+
+fn print_hints(game: &dyn HuntTheWumpus) {
+  for assessment in game.assess_caverns() {
+    let place = match (&assessment.via, &assessment.cavern) {
+      (Some((cavern, direction)), _) => format!("{} of {}", direction.name(), cavern),
+      (None, Some(cavern)) if !assessment.is_safe() => cavern.clone(),
+      (None, _) => continue,
+    };
+    let verdict = match assessment.is_safe() {
+      true => String::from("safe"),
+      false => assessment
+        .threats
+        .iter()
+        .map(|threat| match threat.danger {
+          Danger::Certain => threat.name.clone(),
+          Danger::Possible => format!("{} ({:.0}%)", threat.name, threat.probability * 100.0),
+        })
+        .collect::<Vec<String>>()
+        .join(", "),
+    };
+    println!("  {}: {}", place, verdict);
   }
 }
 
-fn create_caverns() -> Caverns {
-  let mut caverns = HashSet::new();
-  let mut n_caverns = rand::thread_rng().gen_range(0..=30) + 10;
-  while n_caverns > 0 {
-    caverns.insert(make_name());
-    n_caverns -= 1;
-  }
-  caverns
-}
-
-fn make_name() -> String {
-  let environments = vec![
-    "bright", "humid", "dry", "creepy", "ugly", "foggy", "hot", "cold", "drafty", "dreadful",
-  ];
-
-  let shapes = vec![
-    "round",
-    "square",
-    "oval",
-    "irregular",
-    "long",
-    "craggy",
-    "rough",
-    "tall",
-    "narrow",
-  ];
-
-  let cavern_types = vec![
-    "cavern",
-    "room",
-    "chamber",
-    "catacomb",
-    "crevasse",
-    "cell",
-    "tunnel",
-    "passageway",
-    "hall",
-    "expanse",
-  ];
-
-  let adornments = vec![
-    "smelling of sulphur",
-    "with engravings on the walls",
-    "with a bumpy floor",
-    "",
-    "littered with garbage",
-    "spattered with guano",
-    "with piles of Wumpus droppings",
-    "with bones scattered around",
-    "with a corpse on the floor",
-    "that seems to vibrate",
-    "that feels stuffy",
-    "that fills you with dread",
-  ];
-
-  String::from("A ")
-    + choose_name(environments)
-    + " "
-    + choose_name(shapes)
-    + " "
-    + choose_name(cavern_types)
-    + " "
-    + choose_name(adornments)
-}
-
-fn choose_name(names: Vec<&str>) -> &str {
-  let n = names.len();
-  let choice = rand::thread_rng().gen_range(0..n);
-  names[choice]
-}
-
-fn set_special_caverns(game: &mut Box<dyn HuntTheWumpus>) {
-  let player_cavern = connections::any_cavern(game.caverns());
-  game.set_player_cavern(&player_cavern);
-  game.set_wumpus_cavern(&connections::any_other(&player_cavern, game.caverns()));
-  game.add_bat_cavern(&connections::any_other(&player_cavern, game.caverns()));
-  game.add_bat_cavern(&connections::any_other(&player_cavern, game.caverns()));
-  game.add_bat_cavern(&connections::any_other(&player_cavern, game.caverns()));
-
-  game.add_pit_cavern(&connections::any_other(&player_cavern, game.caverns()));
-  game.add_pit_cavern(&connections::any_other(&player_cavern, game.caverns()));
-  game.add_pit_cavern(&connections::any_other(&player_cavern, game.caverns()));
+fn print_parse_error(error: &ParseError) {
+  match error.reason {
+    ParseErrorReason::UnknownCommand => println!("I don't understand."),
+    ParseErrorReason::InvalidMacroName => {
+      println!("'{}' cannot be used as a macro name.", error.input)
+    }
+    ParseErrorReason::MacroTooDeep => println!("The macro '{}' never ends.", error.input),
+  }
+  if let Some(suggestion) = &error.suggestion {
+    println!("Did you mean '{}'?", suggestion);
+  }
+}
+
+// the config file holds one `define name = commands` line per macro.
+fn load_config(path: &Path, parser: &mut CommandParser) {
+  let Ok(text) = fs::read_to_string(path) else {
+    return;
+  };
+  for (index, line) in text.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    match parser.parse_line(line).as_deref() {
+      Ok([ParsedCommand::Define(name, commands)]) => parser.define_macro(name, commands),
+      _ => eprintln!(
+        "warning: {}:{}: expected `define name = commands`",
+        path.display(),
+        index + 1
+      ),
+    }
+  }
+}
+
+// rewrites the line defining the macro, or adds one, keeping the rest of the file.
+fn save_macro(path: &Path, name: &str, commands: &str) -> io::Result<()> {
+  let text = match fs::read_to_string(path) {
+    Ok(text) => text,
+    Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+    Err(error) => return Err(error),
+  };
+  let definition = format!("define {} = {}", name, commands);
+  let mut lines = vec![];
+  let mut saved = false;
+  for line in text.lines() {
+    if defined_name(line).as_deref() != Some(name) {
+      lines.push(String::from(line));
+    } else if !saved {
+      lines.push(definition.clone());
+      saved = true;
+    }
+  }
+  if !saved {
+    lines.push(definition);
+  }
+  fs::write(path, lines.join("\n") + "\n")
+}
+
+// the macro a `define name = commands` line defines.
+fn defined_name(line: &str) -> Option<String> {
+  let (define, definition) = line.trim().split_once(char::is_whitespace)?;
+  if !define.eq_ignore_ascii_case("define") {
+    return None;
+  }
+  let (name, _) = definition.split_once('=')?;
+  Some(name.trim().to_lowercase())
+}
+
+fn print_help() {
+  println!("Commands:");
+  println!("  n, north, go north    move north (also south, east and west)");
+  println!("  sn, s n, shoot north  shoot an arrow north");
+  println!("  r, rest               rest to recover hit points");
+  println!("  status                describe the cavern again");
+  println!("  map, map list         show the caverns you have found, drawn or listed");
+  println!("  goto 3, goto <name>   walk back to a cavern you have seen, numbered in map list");
+  println!("  hint                  tell which caverns may be dangerous, from what you sensed");
+  println!("  n n e, n;n;sw         run several commands, one turn each");
+  println!("  define scout = n s    name a sequence of commands, saved in the config file");
+  println!("  help                  show this help");
+  println!("  q, quit               leave the game");
+}
+
+fn build_game(
+  options: &Options,
+  message_receiver: Box<dyn HtwMessageReceiver>,
+) -> Result<Box<dyn HuntTheWumpus>, Box<dyn Error>> {
+  let setup = game_setup(options);
+  let mut rng = match options.seed {
+    Some(seed) => StdRng::seed_from_u64(seed),
+    None => StdRng::from_entropy(),
+  };
+  let cave = match &options.map_file {
+    Some(path) => {
+      let text = fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {}", path.display(), error))?;
+      map_file::load_map(&text)?
+    }
+    None => setup.generate_cave(&mut rng)?,
+  };
+  Ok(setup.build(cave, &mut rng, message_receiver)?)
+}
+
+// the preset of the difficulty, with the options that override it.
+fn game_setup(options: &Options) -> GameSetup {
+  let mut preset = match options.difficulty {
+    DifficultyOption::Easy => Difficulty::Easy,
+    DifficultyOption::Normal => Difficulty::Normal,
+    DifficultyOption::Hard => Difficulty::Hard,
+  }
+  .preset();
+  if let Some(bats) = options.bats {
+    preset.bats = bats as usize;
+  }
+  if let Some(pits) = options.pits {
+    preset.pits = pits as usize;
+  }
+  if let Some(arrows) = options.arrows {
+    preset.arrows = arrows;
+  }
+  if let Some(hit_points) = options.hit_points {
+    preset.config.max_hit_points = hit_points;
+  }
+  let mut setup = GameSetup::new(preset);
+  setup.generator = match options.generator {
+    Generator::Random => MapGenerator::Random,
+    Generator::Grid => MapGenerator::Grid,
+  };
+  setup.caverns = options.caverns.map(|n| n as usize);
+  setup
+}
+
+#[cfg(test)]
+mod tests_for_main {
+  use super::*;
+  use htw_core::connection::connection::Connection;
+  use htw_core::connections::connections::Connections;
+  use htw_core::direction::direction::Direction;
+  use htw_core::game_builder::game_builder::GameBuilder;
+  use htw_core::htw_game::htw_game::{Turn, TurnHook, TurnPhase};
+  use std::collections::BTreeSet;
+
+  // wakes the wumpus on the third turn and brings it north of b.
+  struct WumpusComes {
+    turns: u32,
+  }
+
+  impl TurnHook for WumpusComes {
+    fn run(&mut self, turn: &mut Turn) {
+      self.turns += 1;
+      if self.turns == 3 {
+        turn.move_wumpus("w");
+      }
+    }
+  }
+
+  // a - b - c from west to east, and w and x north of b.
+  fn set_up() -> Box<dyn HuntTheWumpus> {
+    let mut connections = vec![];
+    for (from, to, direction) in [
+      ("a", "b", Direction::East),
+      ("b", "c", Direction::East),
+      ("b", "w", Direction::North),
+      ("w", "x", Direction::North),
+    ] {
+      connections.push(Connection::new(from, to, &direction));
+      connections.push(Connection::new(to, from, &direction.opposite()));
+    }
+    let caverns: BTreeSet<String> = ["a", "b", "c", "w", "x"]
+      .into_iter()
+      .map(String::from)
+      .collect();
+    GameBuilder::new(caverns)
+      .connections(Connections::new(connections))
+      .player_cavern("a")
+      .wumpus_cavern("x")
+      .message_receiver(Box::new(HtwMessageLog::new()))
+      .turn_hook(TurnPhase::WumpusTurn, Box::new(WumpusComes { turns: 0 }))
+      .seed(1)
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_goto_stops_for_new_percept() {
+    let mut game = set_up();
+    for _ in 0..2 {
+      game.make_move_command(Direction::East);
+      game.execute_command();
+    }
+    assert_eq!("c", game.get_player_cavern());
+    assert_eq!(Outcome::Interrupted, run_goto("a", game.as_mut()));
+    assert_eq!("b", game.get_player_cavern());
+    assert_eq!(Outcome::Turn, run_goto("c", game.as_mut()));
+    assert_eq!(Outcome::NoTurn, run_goto("c", game.as_mut()));
+  }
+
+  #[test]
+  fn test_save_macro_redefines() {
+    let path = env::temp_dir().join(format!("htw_test_{}.config", process::id()));
+    fs::write(&path, "# my macros\nDefine Scout = n s\ndefine back = s\n").unwrap();
+    save_macro(&path, "scout", "n n").unwrap();
+    save_macro(&path, "run", "e e").unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+      "# my macros\ndefine scout = n n\ndefine back = s\ndefine run = e e\n",
+      text
+    );
+  }
 }
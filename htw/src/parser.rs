@@ -0,0 +1,583 @@
+pub mod parser {
+    use htw_core::direction::direction::Direction;
+    use std::collections::HashMap;
+
+    const MOVE_VERBS: [&str; 3] = ["go", "move", "walk"];
+    const SHOOT_VERBS: [&str; 3] = ["shoot", "fire", "s"];
+    // macros may use other macros, but not endlessly.
+    const MAX_MACRO_DEPTH: u32 = 10;
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ParsedCommand {
+        Move(Direction),
+        Shoot(Direction),
+        Rest,
+        Help,
+        Quit,
+        Status,
+        Map(MapView),
+        Hint,
+        // a cavern the player has been to, by name, the start of its name, or its number on
+        // the map list.
+        Goto(String),
+        // a macro name and the commands it stands for.
+        Define(String, String),
+    }
+
+    // how the `map` command shows what the player has found out.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MapView {
+        Drawing,
+        List,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum ParseErrorReason {
+        UnknownCommand,
+        InvalidMacroName,
+        MacroTooDeep,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ParseError {
+        pub input: String,
+        pub reason: ParseErrorReason,
+        // the closest command the parser knows, e.g. "shoot north" for "shot north".
+        pub suggestion: Option<String>,
+    }
+
+    pub struct CommandParser {
+        aliases: HashMap<String, String>,
+        macros: HashMap<String, String>,
+    }
+
+    impl CommandParser {
+        pub fn new() -> CommandParser {
+            let mut parser = CommandParser {
+                aliases: HashMap::new(),
+                macros: HashMap::new(),
+            };
+            for direction in DIRECTIONS {
+                let name = direction.name().to_lowercase();
+                parser.add_alias(&format!("s{}", &name[..1]), &format!("shoot {}", name));
+            }
+            parser.add_alias("r", "rest");
+            parser.add_alias("h", "help");
+            parser.add_alias("?", "help");
+            parser.add_alias("q", "quit");
+            parser.add_alias("exit", "quit");
+            parser.add_alias("look", "status");
+            parser
+        }
+
+        // an alias stands for one or more words, e.g. "sn" for "shoot north".
+        pub fn add_alias(&mut self, alias: &str, words: &str) {
+            self.aliases
+                .insert(alias.to_lowercase(), words.to_lowercase());
+        }
+
+        pub fn remove_alias(&mut self, alias: &str) {
+            self.aliases.remove(&alias.to_lowercase());
+        }
+
+        pub fn aliases(&self) -> &HashMap<String, String> {
+            &self.aliases
+        }
+
+        // a macro stands for a sequence of commands, e.g. "scout" for "n s".
+        pub fn define_macro(&mut self, name: &str, commands: &str) {
+            self.macros
+                .insert(name.to_lowercase(), commands.to_lowercase());
+        }
+
+        pub fn macros(&self) -> &HashMap<String, String> {
+            &self.macros
+        }
+
+        // a line holds one or more commands, separated by `;` or just by spaces as in "n n e".
+        // a step that is not a single command is read as a run of the longest commands it
+        // starts with, where a one-letter word always stands on its own. so "s e" shoots east,
+        // while "n s e" moves three times.
+        pub fn parse_line(&self, line: &str) -> Result<Vec<ParsedCommand>, ParseError> {
+            let line = line.trim();
+            let mut words = line.splitn(2, char::is_whitespace);
+            if words.next().map(|w| w.to_lowercase()) == Some(String::from("define")) {
+                let definition = self.parse_definition(words.next().unwrap_or(""))?;
+                return Ok(vec![definition]);
+            }
+
+            let line = self.expand_macros(line, 0)?;
+            let mut commands = vec![];
+            for step in line.split(';') {
+                if step.trim().is_empty() {
+                    continue;
+                }
+                if let Ok(command) = self.parse(step) {
+                    commands.push(command);
+                    continue;
+                }
+                let words: Vec<&str> = step.split_whitespace().collect();
+                let mut start = 0;
+                while start < words.len() {
+                    let last = match words[start].chars().count() {
+                        1 => start + 1,
+                        _ => words.len(),
+                    };
+                    let longest = (start + 1..=last).rev().find_map(|end| {
+                        Some((end, self.parse(&words[start..end].join(" ")).ok()?))
+                    });
+                    match longest {
+                        Some((end, command)) => {
+                            commands.push(command);
+                            start = end;
+                        }
+                        None => return self.parse(&words[start..].join(" ")).map(|c| vec![c]),
+                    }
+                }
+            }
+            if commands.is_empty() {
+                return self.parse("").map(|c| vec![c]);
+            }
+            Ok(commands)
+        }
+
+        // reads `name = commands`, the part of a definition after "define".
+        fn parse_definition(&self, definition: &str) -> Result<ParsedCommand, ParseError> {
+            let (name, commands) = definition.split_once('=').unwrap_or((definition, ""));
+            let name = name.trim().to_lowercase();
+            let invalid_name = ParseError {
+                input: name.clone(),
+                reason: ParseErrorReason::InvalidMacroName,
+                suggestion: None,
+            };
+            let taken = self.parse(&name).is_ok() || self.reserved_words().contains(&name);
+            if name.is_empty() || name.contains(char::is_whitespace) || name.contains(';') || taken
+            {
+                return Err(invalid_name);
+            }
+            let commands = commands.trim().to_lowercase();
+            self.parse_line(&commands)?;
+            Ok(ParsedCommand::Define(name, commands))
+        }
+
+        // the words after `goto` or `map` in a step are its argument, e.g. a cavern name, so
+        // they are never read as macros.
+        fn expand_macros(&self, line: &str, depth: u32) -> Result<String, ParseError> {
+            let mut steps = vec![];
+            for step in line.split(';') {
+                let mut words = vec![];
+                let mut argument = false;
+                for word in step.split_whitespace() {
+                    if argument {
+                        words.push(word);
+                        continue;
+                    }
+                    argument = ["goto", "map"].contains(&word.to_lowercase().as_str());
+                    match self.macros.get(&word.to_lowercase()) {
+                        Some(_) if depth >= MAX_MACRO_DEPTH => {
+                            return Err(ParseError {
+                                input: word.to_lowercase(),
+                                reason: ParseErrorReason::MacroTooDeep,
+                                suggestion: None,
+                            })
+                        }
+                        Some(commands) => {
+                            steps.push(words.join(" "));
+                            words.clear();
+                            steps.push(self.expand_macros(commands, depth + 1)?);
+                        }
+                        None => words.push(word),
+                    }
+                }
+                steps.push(words.join(" "));
+            }
+            Ok(steps.join(";"))
+        }
+
+        pub fn parse(&self, input: &str) -> Result<ParsedCommand, ParseError> {
+            let input = input.split_whitespace().collect::<Vec<&str>>().join(" ");
+            let input = input.to_lowercase();
+            // cavern names may hold any word, so they are not read as aliases.
+            if let Some(cavern) = input.strip_prefix("goto ") {
+                return Ok(ParsedCommand::Goto(String::from(cavern)));
+            }
+            let words: Vec<&str> = input
+                .split(' ')
+                .flat_map(|word| match self.aliases.get(word) {
+                    Some(words) => words.split_whitespace().collect(),
+                    None => vec![word],
+                })
+                .collect();
+            let command = match words[..] {
+                ["rest"] => Some(ParsedCommand::Rest),
+                ["help"] => Some(ParsedCommand::Help),
+                ["quit"] => Some(ParsedCommand::Quit),
+                ["status"] => Some(ParsedCommand::Status),
+                ["map"] => Some(ParsedCommand::Map(MapView::Drawing)),
+                ["map", "list"] => Some(ParsedCommand::Map(MapView::List)),
+                ["hint"] => Some(ParsedCommand::Hint),
+                [direction] => Direction::parse(direction).map(ParsedCommand::Move),
+                [verb, direction] if MOVE_VERBS.contains(&verb) => {
+                    Direction::parse(direction).map(ParsedCommand::Move)
+                }
+                [verb, direction] if SHOOT_VERBS.contains(&verb) => {
+                    Direction::parse(direction).map(ParsedCommand::Shoot)
+                }
+                _ => None,
+            };
+            command.ok_or_else(|| ParseError {
+                suggestion: self.suggest(&input),
+                reason: ParseErrorReason::UnknownCommand,
+                input,
+            })
+        }
+
+        // every word the parser knows, for completing input.
+        pub fn words(&self) -> Vec<String> {
+            let mut words = self.reserved_words();
+            words.extend(self.macros.keys().cloned());
+            words.sort();
+            words.dedup();
+            words
+        }
+
+        // the words of the commands and aliases, which no macro may take.
+        fn reserved_words(&self) -> Vec<String> {
+            let mut words: Vec<String> = MOVE_VERBS
+                .iter()
+                .chain(SHOOT_VERBS.iter())
+                .chain(
+                    [
+                        "rest", "help", "quit", "status", "map", "list", "hint", "goto", "define",
+                    ]
+                    .iter(),
+                )
+                .map(|word| String::from(*word))
+                .collect();
+            for direction in DIRECTIONS {
+                words.push(direction.name().to_lowercase());
+            }
+            words.extend(self.aliases.keys().cloned());
+            words
+        }
+
+        fn candidates(&self) -> Vec<String> {
+            let mut candidates = vec![];
+            for direction in DIRECTIONS {
+                candidates.push(direction.name().to_lowercase());
+            }
+            for verb in ["go", "shoot"] {
+                for direction in DIRECTIONS {
+                    candidates.push(format!("{} {}", verb, direction.name().to_lowercase()));
+                }
+            }
+            for command in ["rest", "help", "quit", "status", "map", "map list", "hint"] {
+                candidates.push(String::from(command));
+            }
+            let mut aliases: Vec<&String> = self.aliases.keys().chain(self.macros.keys()).collect();
+            aliases.sort();
+            candidates.extend(aliases.into_iter().cloned());
+            candidates
+        }
+
+        fn suggest(&self, input: &str) -> Option<String> {
+            if input.is_empty() {
+                return None;
+            }
+            let candidates = self.candidates();
+            // a verb missing its direction, like "shoot".
+            let prefix = format!("{} ", input);
+            if let Some(candidate) = candidates.iter().find(|c| c.starts_with(&prefix)) {
+                return Some(candidate.clone());
+            }
+            let max_distance = (input.chars().count() / 3).max(1);
+            candidates
+                .into_iter()
+                .map(|candidate| (edit_distance(input, &candidate), candidate))
+                .filter(|(distance, _)| *distance <= max_distance)
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, candidate)| candidate)
+        }
+    }
+
+    impl Default for CommandParser {
+        fn default() -> CommandParser {
+            CommandParser::new()
+        }
+    }
+
+    // the number of characters to insert, delete or replace to turn one word into the other.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+            for (j, cb) in b.iter().enumerate() {
+                let replace = previous[j] + usize::from(ca != *cb);
+                current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+            previous = current;
+        }
+        previous[b.len()]
+    }
+
+    #[cfg(test)]
+    mod tests_for_parser {
+        use super::*;
+
+        #[test]
+        fn test_parse_moves() {
+            let parser = CommandParser::new();
+            for input in [
+                "n",
+                "north",
+                "go north",
+                "move n",
+                "Walk North",
+                "  go   n ",
+            ] {
+                assert_eq!(
+                    Ok(ParsedCommand::Move(Direction::North)),
+                    parser.parse(input)
+                );
+            }
+            assert_eq!(Ok(ParsedCommand::Move(Direction::South)), parser.parse("s"));
+        }
+
+        #[test]
+        fn test_parse_shots() {
+            let parser = CommandParser::new();
+            for input in ["se", "s e", "shoot east", "fire e"] {
+                assert_eq!(
+                    Ok(ParsedCommand::Shoot(Direction::East)),
+                    parser.parse(input)
+                );
+            }
+        }
+
+        #[test]
+        fn test_parse_other_commands() {
+            let parser = CommandParser::new();
+            assert_eq!(Ok(ParsedCommand::Rest), parser.parse("rest"));
+            assert_eq!(Ok(ParsedCommand::Rest), parser.parse("r"));
+            assert_eq!(Ok(ParsedCommand::Help), parser.parse("help"));
+            assert_eq!(Ok(ParsedCommand::Quit), parser.parse("quit"));
+            assert_eq!(Ok(ParsedCommand::Quit), parser.parse("q"));
+            assert_eq!(Ok(ParsedCommand::Status), parser.parse("status"));
+            assert_eq!(
+                Ok(ParsedCommand::Map(MapView::Drawing)),
+                parser.parse("map")
+            );
+            assert_eq!(
+                Ok(ParsedCommand::Map(MapView::List)),
+                parser.parse("Map List")
+            );
+            assert_eq!(Ok(ParsedCommand::Hint), parser.parse("hint"));
+            assert_eq!(
+                Ok(ParsedCommand::Goto(String::from("a cold room"))),
+                parser.parse("goto  A Cold room")
+            );
+            assert!(parser.parse("goto").is_err());
+        }
+
+        #[test]
+        fn test_parse_error_with_suggestion() {
+            let parser = CommandParser::new();
+            assert_eq!(
+                Err(ParseError {
+                    input: String::from("shot north"),
+                    reason: ParseErrorReason::UnknownCommand,
+                    suggestion: Some(String::from("shoot north")),
+                }),
+                parser.parse("shot north")
+            );
+            assert_eq!(
+                Some(String::from("shoot north")),
+                parser.parse("shoot").unwrap_err().suggestion
+            );
+            assert_eq!(
+                Some(String::from("status")),
+                parser.parse("stats").unwrap_err().suggestion
+            );
+        }
+
+        #[test]
+        fn test_parse_error_without_suggestion() {
+            let parser = CommandParser::new();
+            assert_eq!(None, parser.parse("dance wildly").unwrap_err().suggestion);
+            assert_eq!(None, parser.parse("").unwrap_err().suggestion);
+        }
+
+        #[test]
+        fn test_custom_alias() {
+            let mut parser = CommandParser::new();
+            parser.add_alias("up", "go north");
+            parser.add_alias("zap", "shoot");
+            assert_eq!(
+                Ok(ParsedCommand::Move(Direction::North)),
+                parser.parse("up")
+            );
+            assert_eq!(
+                Ok(ParsedCommand::Shoot(Direction::West)),
+                parser.parse("zap w")
+            );
+
+            parser.remove_alias("sn");
+            assert!(parser.parse("sn").is_err());
+        }
+
+        #[test]
+        fn test_parse_line() {
+            let parser = CommandParser::new();
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::East)
+                ]),
+                parser.parse_line("n n e")
+            );
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Shoot(Direction::West)
+                ]),
+                parser.parse_line("n;n;sw")
+            );
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Shoot(Direction::East)
+                ]),
+                parser.parse_line("go north shoot e")
+            );
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::South),
+                    ParsedCommand::Move(Direction::East)
+                ]),
+                parser.parse_line("n s e")
+            );
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Shoot(Direction::East)
+                ]),
+                parser.parse_line("n; s e")
+            );
+            assert_eq!(
+                Ok(vec![ParsedCommand::Move(Direction::North)]),
+                parser.parse_line(" ; n ;")
+            );
+        }
+
+        #[test]
+        fn test_parse_line_error() {
+            let parser = CommandParser::new();
+            let error = parser.parse_line("n shot north").unwrap_err();
+            assert_eq!("shot north", error.input);
+            assert_eq!(Some(String::from("shoot north")), error.suggestion);
+            assert!(parser.parse_line("").is_err());
+        }
+
+        #[test]
+        fn test_define_macro() {
+            let mut parser = CommandParser::new();
+            assert_eq!(
+                Ok(vec![ParsedCommand::Define(
+                    String::from("scout"),
+                    String::from("n s")
+                )]),
+                parser.parse_line("define Scout = N S")
+            );
+            parser.define_macro("scout", "n s");
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Move(Direction::South),
+                    ParsedCommand::Rest
+                ]),
+                parser.parse_line("n scout; rest")
+            );
+        }
+
+        #[test]
+        fn test_define_invalid_macro() {
+            let parser = CommandParser::new();
+            for definition in [
+                "define n = s",
+                "define sn = s",
+                "define = n",
+                "define a b = n",
+                "define goto = n",
+                "define map = n",
+                "define list = n",
+            ] {
+                assert_eq!(
+                    ParseErrorReason::InvalidMacroName,
+                    parser.parse_line(definition).unwrap_err().reason
+                );
+            }
+            assert_eq!(
+                ParseErrorReason::UnknownCommand,
+                parser
+                    .parse_line("define scout = dance")
+                    .unwrap_err()
+                    .reason
+            );
+        }
+
+        #[test]
+        fn test_macro_in_goto_and_map() {
+            let mut parser = CommandParser::new();
+            parser.define_macro("cold", "n");
+            parser.define_macro("list", "s");
+            assert_eq!(
+                Ok(vec![
+                    ParsedCommand::Move(Direction::North),
+                    ParsedCommand::Goto(String::from("cold room")),
+                    ParsedCommand::Map(MapView::List),
+                ]),
+                parser.parse_line("cold; goto Cold Room; map list")
+            );
+        }
+
+        #[test]
+        fn test_recursive_macro() {
+            let mut parser = CommandParser::new();
+            parser.define_macro("there", "n back");
+            parser.define_macro("back", "s there");
+            assert_eq!(
+                ParseErrorReason::MacroTooDeep,
+                parser.parse_line("there").unwrap_err().reason
+            );
+        }
+
+        #[test]
+        fn test_words() {
+            let mut parser = CommandParser::new();
+            parser.define_macro("scout", "n s");
+            let words = parser.words();
+            for word in ["north", "shoot", "go", "rest", "define", "sn", "scout"] {
+                assert!(words.contains(&String::from(word)));
+            }
+        }
+
+        #[test]
+        fn test_edit_distance() {
+            assert_eq!(0, edit_distance("rest", "rest"));
+            assert_eq!(1, edit_distance("shot", "shoot"));
+            assert_eq!(2, edit_distance("nroth", "north"));
+            assert_eq!(4, edit_distance("", "quit"));
+        }
+    }
+}
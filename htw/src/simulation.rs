@@ -0,0 +1,310 @@
+pub mod simulation {
+    use htw_core::agent::agent::{run_agent, Agent};
+    use htw_core::game_setup::game_setup::GameSetup;
+    use htw_core::htw_game::htw_game::GameStatus;
+    use htw_core::htw_message::htw_message::HtwMessageLog;
+    use rayon::prelude::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::fmt;
+    use std::ops::Range;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum GameResult {
+        Won,
+        Lost,
+        // the turns ran out.
+        Unfinished,
+        // the game could not be set up, e.g. the cave was invalid.
+        Failed,
+    }
+
+    impl fmt::Display for GameResult {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                GameResult::Won => write!(f, "won"),
+                GameResult::Lost => write!(f, "lost"),
+                GameResult::Unfinished => write!(f, "unfinished"),
+                GameResult::Failed => write!(f, "failed"),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct GameRecord {
+        // replays the game with `--seed` and the same options.
+        pub seed: u64,
+        pub caverns: usize,
+        pub result: GameResult,
+        // what killed the player, or why the game could not be set up.
+        pub cause: Option<String>,
+        pub turns: u32,
+        pub arrows_shot: u32,
+        pub hit_points: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    pub struct Statistics {
+        pub games: usize,
+        pub won: usize,
+        pub lost: usize,
+        pub unfinished: usize,
+        pub failed: usize,
+        pub win_rate: f64,
+        // how many players died of each cause.
+        pub deaths: BTreeMap<String, usize>,
+        // how many games could not be set up for each reason.
+        pub failures: BTreeMap<String, usize>,
+        // the averages leave out the games that failed.
+        pub average_turns: f64,
+        pub average_arrows_shot: f64,
+    }
+
+    impl Statistics {
+        pub fn new(records: &[GameRecord]) -> Statistics {
+            let count = |result: GameResult| records.iter().filter(|r| r.result == result).count();
+            let causes = |result: GameResult| {
+                let mut causes = BTreeMap::new();
+                for record in records.iter().filter(|r| r.result == result) {
+                    let cause = record.cause.clone().unwrap_or_default();
+                    *causes.entry(cause).or_insert(0) += 1;
+                }
+                causes
+            };
+            let played: Vec<&GameRecord> = records
+                .iter()
+                .filter(|r| r.result != GameResult::Failed)
+                .collect();
+            let average = |value: fn(&GameRecord) -> u32| {
+                let sum: u32 = played.iter().map(|r| value(r)).sum();
+                ratio(sum as usize, played.len())
+            };
+            Statistics {
+                games: records.len(),
+                won: count(GameResult::Won),
+                lost: count(GameResult::Lost),
+                unfinished: count(GameResult::Unfinished),
+                failed: count(GameResult::Failed),
+                win_rate: ratio(count(GameResult::Won), records.len()),
+                deaths: causes(GameResult::Lost),
+                failures: causes(GameResult::Failed),
+                average_turns: average(|r| r.turns),
+                average_arrows_shot: average(|r| r.arrows_shot),
+            }
+        }
+    }
+
+    fn ratio(part: usize, whole: usize) -> f64 {
+        if whole == 0 {
+            0.0
+        } else {
+            part as f64 / whole as f64
+        }
+    }
+
+    // plays the game that `seed` sets up, as `--seed` would.
+    pub fn play(setup: &GameSetup, seed: u64, agent: &mut dyn Agent, max_turns: u32) -> GameRecord {
+        let mut record = GameRecord {
+            seed,
+            caverns: 0,
+            result: GameResult::Failed,
+            cause: None,
+            turns: 0,
+            arrows_shot: 0,
+            hit_points: 0,
+        };
+        let log = HtwMessageLog::new();
+        let mut game = match setup.build_from_seed(seed, Box::new(log.clone())) {
+            Ok(game) => game,
+            Err(error) => {
+                record.cause = Some(error.to_string());
+                return record;
+            }
+        };
+        let outcome = run_agent(game.as_mut(), &log, agent, max_turns);
+        record.caverns = game.caverns().len();
+        (record.result, record.cause) = match outcome.status {
+            GameStatus::Playing => (GameResult::Unfinished, None),
+            GameStatus::Won => (GameResult::Won, None),
+            GameStatus::Lost(cause) => (GameResult::Lost, Some(cause.to_string())),
+        };
+        record.turns = outcome.turns;
+        record.arrows_shot = outcome.arrows_shot;
+        record.hit_points = outcome.hit_points;
+        record
+    }
+
+    // plays a game for each seed, spread over the cores. the records keep the order of the
+    // seeds, and each game gets an agent of its own.
+    pub fn simulate(
+        setup: &GameSetup,
+        seeds: Range<u64>,
+        max_turns: u32,
+        make_agent: &(dyn Fn(u64) -> Box<dyn Agent> + Sync),
+    ) -> Vec<GameRecord> {
+        seeds
+            .into_par_iter()
+            .map(|seed| play(setup, seed, make_agent(seed).as_mut(), max_turns))
+            .collect()
+    }
+
+    // one line for each game, after a header line.
+    pub fn to_csv(records: &[GameRecord]) -> String {
+        let mut csv = String::from("seed,caverns,result,cause,turns,arrows_shot,hit_points\n");
+        for r in records {
+            csv += &format!(
+                "{},{},{},{},{},{},{}\n",
+                r.seed,
+                r.caverns,
+                r.result,
+                escape_csv(r.cause.as_deref().unwrap_or("")),
+                r.turns,
+                r.arrows_shot,
+                r.hit_points
+            );
+        }
+        csv
+    }
+
+    #[derive(Serialize)]
+    struct Report<'a> {
+        statistics: &'a Statistics,
+        games: &'a [GameRecord],
+    }
+
+    // the statistics and every game.
+    pub fn to_json(statistics: &Statistics, records: &[GameRecord]) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&Report {
+            statistics,
+            games: records,
+        })
+    }
+
+    fn escape_csv(field: &str) -> String {
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            String::from(field)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_for_simulation {
+        use super::*;
+        use htw_core::agent::agent::{LogicalAgent, RandomAgent};
+        use htw_core::difficulty::difficulty::Difficulty;
+
+        fn set_up() -> GameSetup {
+            let mut setup = GameSetup::new(Difficulty::Easy.preset());
+            setup.caverns = Some(12);
+            setup
+        }
+
+        fn record(result: GameResult, cause: Option<&str>, turns: u32) -> GameRecord {
+            GameRecord {
+                seed: 0,
+                caverns: 12,
+                result,
+                cause: cause.map(String::from),
+                turns,
+                arrows_shot: 1,
+                hit_points: 10,
+            }
+        }
+
+        #[test]
+        fn test_statistics() {
+            let records = vec![
+                record(GameResult::Won, None, 10),
+                record(GameResult::Lost, Some("pits"), 4),
+                record(GameResult::Lost, Some("pits"), 6),
+                record(GameResult::Unfinished, None, 100),
+                record(GameResult::Failed, Some("invalid map"), 0),
+            ];
+            let statistics = Statistics::new(&records);
+            assert_eq!(5, statistics.games);
+            assert_eq!(
+                (1, 2, 1, 1),
+                (
+                    statistics.won,
+                    statistics.lost,
+                    statistics.unfinished,
+                    statistics.failed
+                )
+            );
+            assert_eq!(0.2, statistics.win_rate);
+            assert_eq!(
+                BTreeMap::from([(String::from("pits"), 2)]),
+                statistics.deaths
+            );
+            assert_eq!(30.0, statistics.average_turns);
+            assert_eq!(1.0, statistics.average_arrows_shot);
+        }
+
+        #[test]
+        fn test_statistics_without_games() {
+            let statistics = Statistics::new(&[]);
+            assert_eq!(0.0, statistics.win_rate);
+            assert_eq!(0.0, statistics.average_turns);
+        }
+
+        #[test]
+        fn test_simulate() {
+            let make_agent = |_| Box::new(LogicalAgent::new()) as Box<dyn Agent>;
+            let records = simulate(&set_up(), 5..13, 200, &make_agent);
+            assert_eq!(
+                (5..13).collect::<Vec<u64>>(),
+                records.iter().map(|r| r.seed).collect::<Vec<u64>>()
+            );
+            assert!(records
+                .iter()
+                .all(|r| r.result != GameResult::Failed && r.caverns == 12));
+            assert_eq!(records, simulate(&set_up(), 5..13, 200, &make_agent));
+        }
+
+        #[test]
+        fn test_simulate_replays_seed() {
+            let make_agent = |seed| Box::new(RandomAgent::new(seed)) as Box<dyn Agent>;
+            let records = simulate(&set_up(), 0..4, 50, &make_agent);
+            assert_eq!(records[3], play(&set_up(), 3, &mut RandomAgent::new(3), 50));
+        }
+
+        #[test]
+        fn test_simulate_failed_setup() {
+            let mut setup = set_up();
+            setup.caverns = Some(3);
+            let make_agent = |_| Box::new(LogicalAgent::new()) as Box<dyn Agent>;
+            let records = simulate(&setup, 0..2, 10, &make_agent);
+            assert_eq!(GameResult::Failed, records[0].result);
+            assert_eq!(
+                Some("3 caverns cannot hold the player, the wumpus, 2 bats and 2 pits"),
+                records[0].cause.as_deref()
+            );
+        }
+
+        #[test]
+        fn test_to_csv() {
+            let records = vec![
+                record(GameResult::Won, None, 10),
+                record(GameResult::Failed, Some("invalid map: \"a\", \"b\""), 0),
+            ];
+            assert_eq!(
+                "seed,caverns,result,cause,turns,arrows_shot,hit_points\n\
+                 0,12,won,,10,1,10\n\
+                 0,12,failed,\"invalid map: \"\"a\"\", \"\"b\"\"\",0,1,10\n",
+                to_csv(&records)
+            );
+        }
+
+        #[test]
+        fn test_to_json() {
+            let records = vec![record(GameResult::Lost, Some("pits"), 4)];
+            let json = to_json(&Statistics::new(&records), &records).unwrap();
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(1, value["statistics"]["deaths"]["pits"]);
+            assert_eq!("lost", value["games"][0]["result"]);
+            assert_eq!(4, value["games"][0]["turns"]);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+pub mod tui {
+    use htw::english_message_receiver::english_htw_message_receiver::english_text;
+    use htw_core::direction::direction::Direction;
+    use htw_core::htw_game::htw_game::GameStatus;
+    use htw_core::htw_message::htw_message::{HtwMessage, HtwMessageLog};
+    use htw_core::HuntTheWumpus;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::{Color, Style, Stylize};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::{Block, Gauge, Paragraph, Wrap};
+    use ratatui::{DefaultTerminal, Frame};
+    use std::io;
+
+    enum Action {
+        Move(Direction),
+        Shoot(Direction),
+        Rest,
+        Quit,
+    }
+
+    struct App {
+        game: Box<dyn HuntTheWumpus>,
+        log: HtwMessageLog,
+        lines: Vec<Line<'static>>,
+        turn: u32,
+    }
+
+    impl App {
+        fn new(game: Box<dyn HuntTheWumpus>, log: HtwMessageLog) -> App {
+            game.report_status();
+            let mut app = App {
+                game,
+                log,
+                lines: vec![],
+                turn: 0,
+            };
+            let messages = app.log.take_messages();
+            app.show(messages);
+            app
+        }
+
+        fn play(&mut self, action: &Action) {
+            match action {
+                Action::Move(direction) => self.game.make_move_command(*direction),
+                Action::Shoot(direction) => self.game.make_shoot_command(*direction),
+                Action::Rest => self.game.make_rest_command(),
+                Action::Quit => return,
+            }
+            self.game.execute_command();
+            self.turn += 1;
+
+            let messages = self.log.take_messages();
+            self.lines
+                .push(Line::from(format!("-- turn {} --", self.turn)).dark_gray());
+            self.show(messages);
+        }
+
+        fn show(&mut self, messages: Vec<HtwMessage>) {
+            for message in messages {
+                let line = Line::from(english_text(&message));
+                let line = match message {
+                    HtwMessage::Passage(_) => line,
+                    HtwMessage::HearBats
+                    | HtwMessage::HearPit
+                    | HtwMessage::SmellWumpus
+                    | HtwMessage::SeeWebs
+                    | HtwMessage::SmellGas => line.cyan(),
+                    HtwMessage::PlayerKillsWumpus => line.green().bold(),
+                    _ => line.yellow(),
+                };
+                self.lines.push(line);
+            }
+        }
+
+        fn is_over(&self) -> bool {
+            self.game.get_status() != &GameStatus::Playing
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            let [main, hints] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            let [map, side] =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .areas(main);
+            let [status, log] =
+                Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).areas(side);
+            self.draw_map(frame, map);
+            self.draw_status(frame, status);
+            self.draw_log(frame, log);
+            self.draw_hints(frame, hints);
+        }
+
+        fn draw_map(&self, frame: &mut Frame, area: Rect) {
+            let block = Block::bordered().title(" Map ");
+            let inner = block.inner(area);
+            let lines: Vec<Line> = self
+                .game
+                .knowledge_map()
+                .draw(self.game.get_player_cavern(), inner.width, inner.height)
+                .into_iter()
+                .map(|row| {
+                    Line::from(
+                        row.chars()
+                            .map(|c| match c {
+                                '@' => Span::styled("@", Style::new().fg(Color::Yellow).bold()),
+                                '!' => Span::styled("!", Style::new().fg(Color::Red)),
+                                '?' => Span::styled("?", Style::new().fg(Color::DarkGray)),
+                                c => Span::raw(c.to_string()),
+                            })
+                            .collect::<Vec<Span>>(),
+                    )
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(lines).block(block), area);
+        }
+
+        fn draw_status(&self, frame: &mut Frame, area: Rect) {
+            let block = Block::bordered().title(" Status ");
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            let [cavern, health, counts] = Layout::vertical([
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(inner);
+
+            let title = match self.game.get_status() {
+                GameStatus::Playing => Line::from(self.game.get_player_cavern().to_string()),
+                GameStatus::Won => Line::from("You killed the Wumpus!").green().bold(),
+                GameStatus::Lost(_) => Line::from("You are dead.").red().bold(),
+            };
+            frame.render_widget(Paragraph::new(title).wrap(Wrap { trim: true }), cavern);
+
+            let hit_points = self.game.get_hit_points();
+            let max_hit_points = self.game.get_max_hit_points().max(1);
+            let ratio = f64::from(hit_points.min(max_hit_points)) / f64::from(max_hit_points);
+            let color = match ratio {
+                r if r > 0.5 => Color::Green,
+                r if r > 0.25 => Color::Yellow,
+                _ => Color::Red,
+            };
+            let gauge = Gauge::default()
+                .gauge_style(Style::new().fg(color))
+                .ratio(ratio)
+                .label(format!("Health {}/{}", hit_points, max_hit_points));
+            frame.render_widget(gauge, health);
+
+            let counts_line = format!("Arrows: {}   Turn: {}", self.game.get_quiver(), self.turn);
+            frame.render_widget(Paragraph::new(counts_line), counts);
+        }
+
+        fn draw_log(&self, frame: &mut Frame, area: Rect) {
+            let block = Block::bordered().title(" Log ");
+            let height = block.inner(area).height as usize;
+            let first = self.lines.len().saturating_sub(height);
+            let lines = self.lines[first..].to_vec();
+            frame.render_widget(Paragraph::new(lines).block(block), area);
+        }
+
+        fn draw_hints(&self, frame: &mut Frame, area: Rect) {
+            let hints = match self.is_over() {
+                true => " press any key to leave",
+                false => " arrows/n e s w: move   Shift+arrows/N E S W: shoot   r: rest   q: quit",
+            };
+            frame.render_widget(Line::from(hints).reversed(), area);
+        }
+    }
+
+    fn read_action() -> io::Result<Option<Action>> {
+        let Event::Key(key) = event::read()? else {
+            return Ok(None);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(None);
+        }
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let direction = match key.code {
+            KeyCode::Up | KeyCode::Char('n' | 'N' | 'k' | 'K') => Some(Direction::North),
+            KeyCode::Down | KeyCode::Char('s' | 'S' | 'j' | 'J') => Some(Direction::South),
+            KeyCode::Right | KeyCode::Char('e' | 'E' | 'l' | 'L') => Some(Direction::East),
+            KeyCode::Left | KeyCode::Char('w' | 'W' | 'h' | 'H') => Some(Direction::West),
+            _ => None,
+        };
+        let uppercase = matches!(key.code, KeyCode::Char(c) if c.is_ascii_uppercase());
+        let action = match (key.code, direction) {
+            (_, Some(direction)) if shift || uppercase => Some(Action::Shoot(direction)),
+            (_, Some(direction)) => Some(Action::Move(direction)),
+            (KeyCode::Char('r'), _) => Some(Action::Rest),
+            (KeyCode::Char('c'), _) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Action::Quit)
+            }
+            (KeyCode::Char('q') | KeyCode::Esc, _) => Some(Action::Quit),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn run_app(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| app.draw(frame))?;
+            if app.is_over() {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        return Ok(());
+                    }
+                }
+                continue;
+            }
+            match read_action()? {
+                Some(Action::Quit) => return Ok(()),
+                Some(action) => app.play(&action),
+                None => {}
+            }
+        }
+    }
+
+    // plays the game full screen. the game must send its messages to `log`.
+    pub fn run(game: Box<dyn HuntTheWumpus>, log: HtwMessageLog) -> io::Result<()> {
+        let app = App::new(game, log);
+        let mut terminal = ratatui::init();
+        let result = run_app(&mut terminal, app);
+        ratatui::restore();
+        result
+    }
+}